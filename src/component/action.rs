@@ -0,0 +1,407 @@
+//! This module provides a typed view of the actions inside of a transaction.
+//!
+//! Only the public parts of each action are kept; anything shielded is dropped.
+use anyhow::anyhow;
+use penumbra_asset::{asset::Id as AssetId, Value};
+use penumbra_dex::{
+    lp::position::{self, Position},
+    TradingPair,
+};
+use penumbra_keys::address::Address;
+use penumbra_num::Amount;
+use penumbra_proto::penumbra::core::{component::governance::v1 as gov_pb, transaction::v1 as pb};
+use penumbra_stake::IdentityKey;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+/// Convert a field that should be present into its domain type.
+fn required<P, D>(field: Option<P>, name: &'static str) -> anyhow::Result<D>
+where
+    D: TryFrom<P>,
+    anyhow::Error: From<D::Error>,
+{
+    Ok(D::try_from(
+        field.ok_or(anyhow!("action missing {}", name))?,
+    )?)
+}
+
+/// Get a readable name for a governance vote.
+pub fn vote_name(vote: Option<gov_pb::Vote>) -> String {
+    match vote.map(|x| x.vote) {
+        Some(1) => "abstain",
+        Some(2) => "yes",
+        Some(3) => "no",
+        _ => "unspecified",
+    }
+    .to_string()
+}
+
+/// One of the actions in a transaction.
+///
+/// Actions like spends and outputs are entirely shielded, so they carry no data.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Action {
+    Spend,
+    Output,
+    Swap {
+        #[serde_as(as = "DisplayFromStr")]
+        asset_1: AssetId,
+        #[serde_as(as = "DisplayFromStr")]
+        asset_2: AssetId,
+        /// The amount of the first asset being swapped.
+        #[serde_as(as = "DisplayFromStr")]
+        delta_1: Amount,
+        /// The amount of the second asset being swapped.
+        #[serde_as(as = "DisplayFromStr")]
+        delta_2: Amount,
+    },
+    SwapClaim {
+        #[serde_as(as = "DisplayFromStr")]
+        asset_1: AssetId,
+        #[serde_as(as = "DisplayFromStr")]
+        asset_2: AssetId,
+        /// The height of the batch swap being claimed.
+        height: u64,
+        #[serde_as(as = "DisplayFromStr")]
+        fee: Amount,
+    },
+    ValidatorDefinition {
+        #[serde_as(as = "DisplayFromStr")]
+        identity: IdentityKey,
+        name: String,
+        sequence_number: u32,
+    },
+    IbcRelay {
+        /// The type of the wrapped IBC message.
+        type_url: String,
+    },
+    ProposalSubmit {
+        proposal: u64,
+        title: String,
+        #[serde_as(as = "DisplayFromStr")]
+        deposit_amount: Amount,
+    },
+    ProposalWithdraw {
+        proposal: u64,
+        reason: String,
+    },
+    ValidatorVote {
+        proposal: u64,
+        #[serde_as(as = "DisplayFromStr")]
+        identity: IdentityKey,
+        vote: String,
+    },
+    DelegatorVote {
+        proposal: u64,
+        vote: String,
+        #[serde_as(as = "DisplayFromStr")]
+        unbonded_amount: Amount,
+    },
+    ProposalDepositClaim {
+        proposal: u64,
+        #[serde_as(as = "DisplayFromStr")]
+        deposit_amount: Amount,
+    },
+    PositionOpen {
+        #[serde_as(as = "DisplayFromStr")]
+        position_id: position::Id,
+        #[serde_as(as = "DisplayFromStr")]
+        asset_1: AssetId,
+        #[serde_as(as = "DisplayFromStr")]
+        asset_2: AssetId,
+        #[serde_as(as = "DisplayFromStr")]
+        reserves_1: Amount,
+        #[serde_as(as = "DisplayFromStr")]
+        reserves_2: Amount,
+        /// The fee charged by the position, in basis points.
+        fee_bps: u32,
+    },
+    PositionClose {
+        #[serde_as(as = "DisplayFromStr")]
+        position_id: position::Id,
+    },
+    PositionWithdraw {
+        #[serde_as(as = "DisplayFromStr")]
+        position_id: position::Id,
+    },
+    PositionRewardClaim {
+        #[serde_as(as = "DisplayFromStr")]
+        position_id: position::Id,
+    },
+    Delegate {
+        #[serde_as(as = "DisplayFromStr")]
+        identity: IdentityKey,
+        /// The amount of the staking token being delegated.
+        #[serde_as(as = "DisplayFromStr")]
+        unbonded_amount: Amount,
+        /// The amount of delegation tokens received.
+        #[serde_as(as = "DisplayFromStr")]
+        delegation_amount: Amount,
+    },
+    Undelegate {
+        #[serde_as(as = "DisplayFromStr")]
+        identity: IdentityKey,
+        /// The amount of the staking token being received.
+        #[serde_as(as = "DisplayFromStr")]
+        unbonded_amount: Amount,
+        /// The amount of delegation tokens being undelegated.
+        #[serde_as(as = "DisplayFromStr")]
+        delegation_amount: Amount,
+        /// The height at which unbonding starts, which identifies the unbonding tokens received.
+        unbonding_start_height: u64,
+    },
+    UndelegateClaim {
+        #[serde_as(as = "DisplayFromStr")]
+        identity: IdentityKey,
+        /// The height at which unbonding started, which identifies the unbonding tokens claimed.
+        unbonding_start_height: u64,
+        /// The penalty applied while unbonding, where 10^8 means that everything is slashed.
+        penalty: u64,
+    },
+    CommunityPoolSpend {
+        #[serde_as(as = "DisplayFromStr")]
+        asset_id: AssetId,
+        #[serde_as(as = "DisplayFromStr")]
+        amount: Amount,
+    },
+    CommunityPoolOutput {
+        #[serde_as(as = "DisplayFromStr")]
+        asset_id: AssetId,
+        #[serde_as(as = "DisplayFromStr")]
+        amount: Amount,
+        #[serde_as(as = "DisplayFromStr")]
+        address: Address,
+    },
+    CommunityPoolDeposit {
+        #[serde_as(as = "DisplayFromStr")]
+        asset_id: AssetId,
+        #[serde_as(as = "DisplayFromStr")]
+        amount: Amount,
+    },
+    Ics20Withdrawal {
+        #[serde_as(as = "DisplayFromStr")]
+        amount: Amount,
+        denom: String,
+        destination_chain_address: String,
+        source_channel: String,
+    },
+    DutchAuctionSchedule,
+    DutchAuctionEnd,
+    DutchAuctionWithdraw,
+    /// An action which couldn't be decoded, e.g. because it's missing a field.
+    Unknown {
+        /// Why the action couldn't be decoded.
+        reason: String,
+    },
+}
+
+impl Action {
+    /// The name of this kind of action.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Spend => "spend",
+            Self::Output => "output",
+            Self::Swap { .. } => "swap",
+            Self::SwapClaim { .. } => "swap_claim",
+            Self::ValidatorDefinition { .. } => "validator_definition",
+            Self::IbcRelay { .. } => "ibc_relay",
+            Self::ProposalSubmit { .. } => "proposal_submit",
+            Self::ProposalWithdraw { .. } => "proposal_withdraw",
+            Self::ValidatorVote { .. } => "validator_vote",
+            Self::DelegatorVote { .. } => "delegator_vote",
+            Self::ProposalDepositClaim { .. } => "proposal_deposit_claim",
+            Self::PositionOpen { .. } => "position_open",
+            Self::PositionClose { .. } => "position_close",
+            Self::PositionWithdraw { .. } => "position_withdraw",
+            Self::PositionRewardClaim { .. } => "position_reward_claim",
+            Self::Delegate { .. } => "delegate",
+            Self::Undelegate { .. } => "undelegate",
+            Self::UndelegateClaim { .. } => "undelegate_claim",
+            Self::CommunityPoolSpend { .. } => "community_pool_spend",
+            Self::CommunityPoolOutput { .. } => "community_pool_output",
+            Self::CommunityPoolDeposit { .. } => "community_pool_deposit",
+            Self::Ics20Withdrawal { .. } => "ics20_withdrawal",
+            Self::DutchAuctionSchedule => "dutch_auction_schedule",
+            Self::DutchAuctionEnd => "dutch_auction_end",
+            Self::DutchAuctionWithdraw => "dutch_auction_withdraw",
+            Self::Unknown { .. } => "unknown",
+        }
+    }
+
+    /// Decode an action, falling back to [`Action::Unknown`] if that fails.
+    ///
+    /// The chain has already accepted these actions, so failing to understand one
+    /// shouldn't prevent us from indexing the rest of the transaction.
+    pub fn decode(action: Option<pb::action::Action>) -> Self {
+        let result = action
+            .ok_or(anyhow!("action missing contents"))
+            .and_then(Self::try_from);
+        match result {
+            Ok(action) => action,
+            Err(e) => {
+                tracing::warn!(?e, "failed to decode action");
+                Self::Unknown {
+                    reason: e.to_string(),
+                }
+            }
+        }
+    }
+}
+
+impl TryFrom<pb::action::Action> for Action {
+    type Error = anyhow::Error;
+
+    fn try_from(action: pb::action::Action) -> Result<Self, Self::Error> {
+        use pb::action::Action as A;
+
+        Ok(match action {
+            A::Spend(_) => Self::Spend,
+            A::Output(_) => Self::Output,
+            A::Swap(x) => {
+                let body = x.body.ok_or(anyhow!("swap missing body"))?;
+                let pair: TradingPair = required(body.trading_pair, "trading_pair")?;
+                Self::Swap {
+                    asset_1: pair.asset_1(),
+                    asset_2: pair.asset_2(),
+                    delta_1: required(body.delta_1_i, "delta_1_i")?,
+                    delta_2: required(body.delta_2_i, "delta_2_i")?,
+                }
+            }
+            A::SwapClaim(x) => {
+                let body = x.body.ok_or(anyhow!("swap claim missing body"))?;
+                let output_data = body
+                    .output_data
+                    .ok_or(anyhow!("swap claim missing output_data"))?;
+                let pair: TradingPair = required(output_data.trading_pair, "trading_pair")?;
+                let fee = match body.fee.and_then(|x| x.amount) {
+                    None => Amount::zero(),
+                    Some(x) => x.try_into()?,
+                };
+                Self::SwapClaim {
+                    asset_1: pair.asset_1(),
+                    asset_2: pair.asset_2(),
+                    height: output_data.height,
+                    fee,
+                }
+            }
+            A::ValidatorDefinition(x) => {
+                let validator = x.validator.ok_or(anyhow!("definition missing validator"))?;
+                Self::ValidatorDefinition {
+                    identity: required(validator.identity_key, "identity_key")?,
+                    name: validator.name,
+                    sequence_number: validator.sequence_number,
+                }
+            }
+            A::IbcRelayAction(x) => Self::IbcRelay {
+                type_url: x.raw_action.map(|x| x.type_url).unwrap_or_default(),
+            },
+            A::ProposalSubmit(x) => {
+                let proposal = x.proposal.ok_or(anyhow!("submission missing proposal"))?;
+                Self::ProposalSubmit {
+                    proposal: proposal.id,
+                    title: proposal.title,
+                    deposit_amount: required(x.deposit_amount, "deposit_amount")?,
+                }
+            }
+            A::ProposalWithdraw(x) => Self::ProposalWithdraw {
+                proposal: x.proposal,
+                reason: x.reason,
+            },
+            A::ValidatorVote(x) => {
+                let body = x.body.ok_or(anyhow!("validator vote missing body"))?;
+                Self::ValidatorVote {
+                    proposal: body.proposal,
+                    identity: required(body.identity_key, "identity_key")?,
+                    vote: vote_name(body.vote),
+                }
+            }
+            A::DelegatorVote(x) => {
+                let body = x.body.ok_or(anyhow!("delegator vote missing body"))?;
+                Self::DelegatorVote {
+                    proposal: body.proposal,
+                    vote: vote_name(body.vote),
+                    unbonded_amount: required(body.unbonded_amount, "unbonded_amount")?,
+                }
+            }
+            A::ProposalDepositClaim(x) => Self::ProposalDepositClaim {
+                proposal: x.proposal,
+                deposit_amount: required(x.deposit_amount, "deposit_amount")?,
+            },
+            A::PositionOpen(x) => {
+                let position: Position = required(x.position, "position")?;
+                Self::PositionOpen {
+                    position_id: position.id(),
+                    asset_1: position.phi.pair.asset_1(),
+                    asset_2: position.phi.pair.asset_2(),
+                    reserves_1: position.reserves.r1,
+                    reserves_2: position.reserves.r2,
+                    fee_bps: position.phi.component.fee,
+                }
+            }
+            A::PositionClose(x) => Self::PositionClose {
+                position_id: required(x.position_id, "position_id")?,
+            },
+            A::PositionWithdraw(x) => Self::PositionWithdraw {
+                position_id: required(x.position_id, "position_id")?,
+            },
+            A::PositionRewardClaim(x) => Self::PositionRewardClaim {
+                position_id: required(x.position_id, "position_id")?,
+            },
+            A::Delegate(x) => Self::Delegate {
+                identity: required(x.validator_identity, "validator_identity")?,
+                unbonded_amount: required(x.unbonded_amount, "unbonded_amount")?,
+                delegation_amount: required(x.delegation_amount, "delegation_amount")?,
+            },
+            A::Undelegate(x) => Self::Undelegate {
+                identity: required(x.validator_identity, "validator_identity")?,
+                unbonded_amount: required(x.unbonded_amount, "unbonded_amount")?,
+                delegation_amount: required(x.delegation_amount, "delegation_amount")?,
+                unbonding_start_height: x
+                    .from_epoch
+                    .ok_or(anyhow!("action missing from_epoch"))?
+                    .start_height,
+            },
+            A::UndelegateClaim(x) => {
+                let body = x.body.ok_or(anyhow!("undelegate claim missing body"))?;
+                Self::UndelegateClaim {
+                    identity: required(body.validator_identity, "validator_identity")?,
+                    unbonding_start_height: body.unbonding_start_height,
+                    penalty: body.penalty.ok_or(anyhow!("action missing penalty"))?.inner,
+                }
+            }
+            A::CommunityPoolSpend(x) => {
+                let value: Value = required(x.value, "value")?;
+                Self::CommunityPoolSpend {
+                    asset_id: value.asset_id,
+                    amount: value.amount,
+                }
+            }
+            A::CommunityPoolOutput(x) => {
+                let value: Value = required(x.value, "value")?;
+                Self::CommunityPoolOutput {
+                    asset_id: value.asset_id,
+                    amount: value.amount,
+                    address: required(x.address, "address")?,
+                }
+            }
+            A::CommunityPoolDeposit(x) => {
+                let value: Value = required(x.value, "value")?;
+                Self::CommunityPoolDeposit {
+                    asset_id: value.asset_id,
+                    amount: value.amount,
+                }
+            }
+            A::Ics20Withdrawal(x) => Self::Ics20Withdrawal {
+                amount: required(x.amount, "amount")?,
+                denom: x.denom.map(|x| x.denom).unwrap_or_default(),
+                destination_chain_address: x.destination_chain_address,
+                source_channel: x.source_channel,
+            },
+            A::ActionDutchAuctionSchedule(_) => Self::DutchAuctionSchedule,
+            A::ActionDutchAuctionEnd(_) => Self::DutchAuctionEnd,
+            A::ActionDutchAuctionWithdraw(_) => Self::DutchAuctionWithdraw,
+        })
+    }
+}
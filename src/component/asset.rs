@@ -0,0 +1,271 @@
+use anyhow::anyhow;
+use axum::async_trait;
+use penumbra_asset::{
+    asset::{Id as AssetId, Metadata, REGISTRY},
+    STAKING_TOKEN_DENOM,
+};
+use penumbra_stake::{DelegationToken, IdentityKey};
+use pindexer::{AppView, ContextualizedEvent, PgPool, PgTransaction};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sqlx::{postgres::PgRow, Executor as _, Postgres, Transaction};
+
+use crate::component::{action::Action, IndexedTransaction};
+use crate::sql::SQLAssetId;
+
+/// The data inside of an ICS20 transfer packet.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FungibleTokenPacketData {
+    pub denom: String,
+    pub amount: String,
+    pub sender: String,
+    pub receiver: String,
+}
+
+impl FungibleTokenPacketData {
+    /// The denom this packet will have on the receiving chain.
+    ///
+    /// Tokens returning to their source have the sending port and channel stripped,
+    /// and other tokens have the receiving port and channel prepended.
+    pub fn receiving_denom(
+        &self,
+        src_port: &str,
+        src_channel: &str,
+        dst_port: &str,
+        dst_channel: &str,
+    ) -> String {
+        let prefix = format!("{src_port}/{src_channel}/");
+        match self.denom.strip_prefix(&prefix) {
+            Some(denom) => denom.to_string(),
+            None => format!("{dst_port}/{dst_channel}/{}", self.denom),
+        }
+    }
+}
+
+/// The metadata of an asset, if we can learn anything from a given action.
+fn action_metadata(action: &Action) -> Option<Metadata> {
+    match action {
+        Action::ValidatorDefinition { identity, .. }
+        | Action::Delegate { identity, .. }
+        | Action::Undelegate { identity, .. } => Some(DelegationToken::new(*identity).denom()),
+        Action::Ics20Withdrawal { denom, .. } => REGISTRY.parse_denom(denom),
+        _ => None,
+    }
+}
+
+/// Remember the metadata of an asset, if we don't already know about it.
+async fn register<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    metadata: &Metadata,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+INSERT INTO asset
+VALUES ($1, $2, $3)
+ON CONFLICT (asset_id) DO NOTHING;"#,
+    )
+    .bind(SQLAssetId::from(metadata.id()))
+    .bind(metadata.base_denom().denom)
+    .bind(serde_json::to_string(metadata)?)
+    .execute(dbtx.as_mut())
+    .await?;
+    Ok(())
+}
+
+/// Learn the metadata of the assets present at genesis.
+fn genesis_metadata(app_state: &serde_json::Value) -> anyhow::Result<Vec<Metadata>> {
+    let content = match super::genesis_content(app_state)? {
+        Some(x) => x,
+        None => return Ok(Vec::new()),
+    };
+    let mut out = Vec::new();
+    for allocation in content
+        .shielded_pool_content
+        .map(|x| x.allocations)
+        .unwrap_or_default()
+    {
+        out.extend(REGISTRY.parse_denom(&allocation.denom));
+    }
+    for validator in content
+        .stake_content
+        .map(|x| x.validators)
+        .unwrap_or_default()
+    {
+        let identity = IdentityKey::try_from(
+            validator
+                .identity_key
+                .ok_or(anyhow!("genesis validator missing identity_key"))?,
+        )?;
+        out.push(DelegationToken::new(identity).denom());
+    }
+    Ok(out)
+}
+
+/// A summary of what we know about an asset.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Asset {
+    #[serde_as(as = "DisplayFromStr")]
+    pub asset_id: AssetId,
+    /// The base denomination, e.g. "upenumbra".
+    pub denom: String,
+    /// The denomination used for display, e.g. "penumbra".
+    pub display: String,
+    /// The power of 10 separating the base and display denominations.
+    pub exponent: u8,
+}
+
+impl From<&Metadata> for Asset {
+    fn from(metadata: &Metadata) -> Self {
+        let unit = metadata.default_unit();
+        Self {
+            asset_id: metadata.id(),
+            denom: metadata.base_denom().denom,
+            display: unit.to_string(),
+            exponent: unit.exponent(),
+        }
+    }
+}
+
+/// A wrapper to decode the stored metadata of an asset.
+struct MetadataRow(Metadata);
+
+impl<'r> sqlx::FromRow<'r, PgRow> for MetadataRow {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let (metadata,): (&'r str,) = sqlx::FromRow::from_row(row)?;
+        let metadata = serde_json::from_str(metadata).map_err(|e| sqlx::Error::ColumnDecode {
+            index: "0".to_string(),
+            source: Box::new(e),
+        })?;
+        Ok(Self(metadata))
+    }
+}
+
+/// A component for learning and retrieving the metadata of assets.
+#[derive(Debug, Clone, Copy)]
+pub struct Component {}
+
+impl Component {
+    pub const TEMPLATES: [(&'static str, &'static str); 2] = [
+        ("assets", include_str!("../../templates/assets.html")),
+        ("asset", include_str!("../../templates/asset.html")),
+    ];
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn attach_to_indexer(self, indexer: pindexer::Indexer) -> pindexer::Indexer {
+        indexer.with_index(self)
+    }
+
+    /// Fetch the metadata of every asset we know about.
+    pub async fn metadata(pool: &PgPool) -> anyhow::Result<Vec<Metadata>> {
+        let rows: Vec<MetadataRow> = sqlx::query_as("SELECT metadata FROM asset;")
+            .fetch_all(pool)
+            .await?;
+        Ok(rows.into_iter().map(|x| x.0).collect())
+    }
+
+    /// Fetch a summary of every asset we know about, sorted by denomination.
+    pub async fn assets(pool: &PgPool) -> anyhow::Result<Vec<Asset>> {
+        let rows: Vec<MetadataRow> = sqlx::query_as("SELECT metadata FROM asset ORDER BY denom;")
+            .fetch_all(pool)
+            .await?;
+        Ok(rows.iter().map(|x| Asset::from(&x.0)).collect())
+    }
+
+    /// Fetch a summary of a specific asset, if we know about it.
+    pub async fn asset(pool: &PgPool, asset_id: AssetId) -> anyhow::Result<Option<Asset>> {
+        let row: Option<MetadataRow> =
+            sqlx::query_as("SELECT metadata FROM asset WHERE asset_id = $1;")
+                .bind(SQLAssetId::from(asset_id))
+                .fetch_optional(pool)
+                .await?;
+        Ok(row.map(|x| Asset::from(&x.0)))
+    }
+
+    /// Figure out which asset a string refers to.
+    ///
+    /// This accepts either an asset id, like `passet1...`, or a base denomination.
+    pub async fn resolve(pool: &PgPool, id_or_denom: &str) -> anyhow::Result<AssetId> {
+        if let Ok(asset_id) = id_or_denom.parse::<AssetId>() {
+            return Ok(asset_id);
+        }
+        let row: Option<(SQLAssetId,)> =
+            sqlx::query_as("SELECT asset_id FROM asset WHERE denom = $1;")
+                .bind(id_or_denom)
+                .fetch_optional(pool)
+                .await?;
+        if let Some((asset_id,)) = row {
+            return Ok(asset_id.into());
+        }
+        // We might not have seen this denomination yet, but we can still tell what its id is.
+        REGISTRY
+            .parse_denom(id_or_denom)
+            .map(|x| x.id())
+            .ok_or(anyhow!("unrecognized asset: {id_or_denom}"))
+    }
+}
+
+#[async_trait]
+impl AppView for Component {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        dbtx.as_mut().execute(include_str!("asset.sql")).await?;
+        register(dbtx, &STAKING_TOKEN_DENOM).await?;
+        // Not knowing about some assets shouldn't prevent us from indexing anything else.
+        match genesis_metadata(app_state) {
+            Ok(metadata) => {
+                for x in metadata.iter() {
+                    register(dbtx, x).await?;
+                }
+            }
+            Err(e) => tracing::warn!(?e, "failed to read asset metadata from genesis"),
+        }
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        type_str == "tx" || type_str == "recv_packet"
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        if event.event.kind == "tx" {
+            let tx = match IndexedTransaction::fetch(src_db, event).await? {
+                Some(tx) if tx.succeeded() => tx,
+                _ => return Ok(()),
+            };
+            for metadata in tx.actions.iter().filter_map(action_metadata) {
+                register(dbtx, &metadata).await?;
+            }
+            return Ok(());
+        }
+        let dst_port = super::attribute(event, "packet_dst_port")?;
+        // Only transfer packets create new assets.
+        if dst_port != "transfer" {
+            return Ok(());
+        }
+        let packet: FungibleTokenPacketData =
+            serde_json::from_str(super::attribute(event, "packet_data")?)?;
+        let denom = packet.receiving_denom(
+            super::attribute(event, "packet_src_port")?,
+            super::attribute(event, "packet_src_channel")?,
+            dst_port,
+            super::attribute(event, "packet_dst_channel")?,
+        );
+        if let Some(metadata) = REGISTRY.parse_denom(&denom) {
+            register(dbtx, &metadata).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,13 @@
+-- This component is responsible for keeping track of the metadata of assets.
+--
+-- Metadata is learned from the genesis state, and as new denominations appear on chain.
+
+CREATE TABLE IF NOT EXISTS asset (
+  asset_id BYTEA PRIMARY KEY,
+  -- The base denomination of the asset, e.g. "upenumbra".
+  denom TEXT NOT NULL,
+  -- The full metadata of the asset, as JSON.
+  metadata TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS asset_denom_idx ON asset (denom);
@@ -1,32 +1,123 @@
 use axum::async_trait;
 use pindexer::{AppView, ContextualizedEvent, PgPool, PgTransaction};
 use serde::{Deserialize, Serialize};
-use sqlx::types::chrono::{DateTime, Utc};
+use sqlx::{
+    postgres::PgRow,
+    types::chrono::{DateTime, Utc},
+    Executor as _,
+};
+use tendermint_rpc::{Client as _, HttpClient};
 
 use crate::pagination::Pagination;
 
 /// Implement a block
-#[derive(Debug, Clone, Copy, sqlx::FromRow, Serialize, Deserialize)]
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
 pub struct Block {
     pub height: i64,
+    pub chain_id: String,
     pub transaction_count: i64,
     pub created_at: DateTime<Utc>,
+    /// The hash of the block, in hex, if we fetched its header.
+    pub hash: Option<String>,
+    /// The address of the validator which proposed the block, in hex, if we fetched its header.
+    pub proposer: Option<String>,
+    /// The app hash of the block, in hex, if we fetched its header.
+    pub app_hash: Option<String>,
+}
+
+/// A transaction included in a block.
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
+pub struct BlockTransaction {
+    /// The position of the transaction in the block.
+    pub index: i32,
+    /// The hash of the transaction, in hex.
+    pub hash: String,
+}
+
+/// A key value pair attached to an event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventAttribute {
+    pub key: String,
+    pub value: String,
+}
+
+/// An event emitted during a block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockEvent {
+    /// The hash of the transaction which emitted this event, in hex, if any.
+    pub tx_hash: Option<String>,
+    /// The type of the event.
+    pub kind: String,
+    pub attributes: Vec<EventAttribute>,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for BlockEvent {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (Option<String>, String, &'r str) = sqlx::FromRow::from_row(row)?;
+        let attributes = serde_json::from_str(x.2).map_err(|e| sqlx::Error::ColumnDecode {
+            index: "2".to_string(),
+            source: Box::new(e),
+        })?;
+        Ok(Self {
+            tx_hash: x.0,
+            kind: x.1,
+            attributes,
+        })
+    }
+}
+
+/// A full description of a block, including its transactions and events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockDetail {
+    pub block: Block,
+    pub transactions: Vec<BlockTransaction>,
+    pub events: Vec<BlockEvent>,
 }
 
 /// A component for indexing and retrieving information about blocks.
-#[derive(Debug, Clone, Copy)]
-pub struct Component {}
+#[derive(Debug, Clone)]
+pub struct Component {
+    /// Used to fetch the parts of each block header which the cometbft sink doesn't record.
+    client: Option<HttpClient>,
+}
 
 impl Component {
-    pub const TEMPLATE: (&'static str, &'static str) =
-        ("block", include_str!("../../templates/blocks.html"));
+    pub const TEMPLATES: [(&'static str, &'static str); 2] = [
+        ("blocks", include_str!("../../templates/blocks.html")),
+        ("block", include_str!("../../templates/block.html")),
+    ];
 
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(cometbft_rpc_url: Option<&str>) -> anyhow::Result<Self> {
+        let client = cometbft_rpc_url.map(HttpClient::new).transpose()?;
+        Ok(Self { client })
     }
 
     pub fn attach_to_indexer(self, indexer: pindexer::Indexer) -> pindexer::Indexer {
-        indexer.with_index(Self {})
+        indexer.with_index(self)
+    }
+
+    /// Fetch the hash, proposer, and app hash of a block, if we have a cometbft node to ask.
+    ///
+    /// Nodes prune old blocks, so failing to get a header shouldn't stop us from indexing.
+    async fn header(&self, height: i64) -> Option<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+        let client = self.client.as_ref()?;
+        let result = async {
+            let response = client.block(u32::try_from(height)?).await?;
+            let header = response.block.header;
+            anyhow::Ok((
+                response.block_id.hash.as_bytes().to_vec(),
+                header.proposer_address.as_bytes().to_vec(),
+                header.app_hash.as_bytes().to_vec(),
+            ))
+        }
+        .await;
+        match result {
+            Ok(x) => Some(x),
+            Err(e) => {
+                tracing::warn!(?e, height, "failed to fetch block header");
+                None
+            }
+        }
     }
 
     /// Fetch a list of blocks.
@@ -34,13 +125,74 @@ impl Component {
     /// This will be sorted in reverse reverse order, by default.
     pub async fn blocks(pool: &PgPool, pagination: &Pagination<i64>) -> anyhow::Result<Vec<Block>> {
         Ok(sqlx::query_as(
-            "SELECT * FROM block WHERE height BETWEEN $1 AND $2 ORDER BY height DESC;",
+            r#"
+SELECT
+    height, chain_id, transaction_count, created_at,
+    encode(hash, 'hex') AS hash, encode(proposer, 'hex') AS proposer, encode(app_hash, 'hex') AS app_hash
+FROM block
+WHERE height BETWEEN $1 AND $2
+ORDER BY height DESC;"#,
         )
         .bind(pagination.start)
         .bind(pagination.stop)
         .fetch_all(pool)
         .await?)
     }
+
+    /// Fetch a specific block, by height, along with its transactions and events.
+    ///
+    /// The events are read straight from the cometbft sink's database, `src_pool`.
+    pub async fn block(
+        pool: &PgPool,
+        src_pool: &PgPool,
+        height: i64,
+    ) -> anyhow::Result<BlockDetail> {
+        let block = sqlx::query_as(
+            r#"
+SELECT
+    height, chain_id, transaction_count, created_at,
+    encode(hash, 'hex') AS hash, encode(proposer, 'hex') AS proposer, encode(app_hash, 'hex') AS app_hash
+FROM block
+WHERE height = $1;"#,
+        )
+        .bind(height)
+        .fetch_one(pool)
+        .await?;
+        let transactions = sqlx::query_as(
+            "SELECT index, encode(hash, 'hex') AS hash FROM block_transaction WHERE height = $1 ORDER BY index;",
+        )
+        .bind(height)
+        .fetch_all(pool)
+        .await?;
+        // The "block" and "tx" events are synthesized by the cometbft sink, so we skip those.
+        let events = sqlx::query_as(
+            r#"
+SELECT
+    lower(tx_results.tx_hash),
+    events.type,
+    COALESCE(
+        (SELECT json_agg(json_build_object('key', attributes.key, 'value', attributes.value))
+         FROM attributes
+         WHERE attributes.event_id = events.rowid),
+        '[]'
+    )::TEXT
+FROM
+    events
+    JOIN blocks ON events.block_id = blocks.rowid
+    LEFT JOIN tx_results ON events.tx_id = tx_results.rowid
+WHERE
+    blocks.height = $1 AND events.type NOT IN ('block', 'tx')
+ORDER BY events.rowid;"#,
+        )
+        .bind(height)
+        .fetch_all(src_pool)
+        .await?;
+        Ok(BlockDetail {
+            block,
+            transactions,
+            events,
+        })
+    }
 }
 
 #[async_trait]
@@ -50,9 +202,7 @@ impl AppView for Component {
         dbtx: &mut PgTransaction,
         _app_state: &serde_json::Value,
     ) -> Result<(), anyhow::Error> {
-        sqlx::query(include_str!("block.sql"))
-            .execute(dbtx.as_mut())
-            .await?;
+        dbtx.as_mut().execute(include_str!("block.sql")).await?;
         Ok(())
     }
 
@@ -66,10 +216,12 @@ impl AppView for Component {
         event: &ContextualizedEvent,
         src_db: &PgPool,
     ) -> Result<(), anyhow::Error> {
-        let row: (i64, DateTime<Utc>, i64) = sqlx::query_as(
+        let row: (i64, i64, String, DateTime<Utc>, i64) = sqlx::query_as(
             r#"
 SELECT
+    blocks.rowid,
     blocks.height,
+    blocks.chain_id,
     blocks.created_at,
     (SELECT count(*) FROM tx_results WHERE tx_results.block_id = blocks.rowid)
 FROM 
@@ -80,18 +232,42 @@ WHERE
         .bind(event.local_rowid)
         .fetch_one(src_db)
         .await?;
+        let (block_id, height) = (row.0, row.1);
+        let header = self.header(height).await;
 
         sqlx::query(
             r#"
 INSERT INTO block
-VALUES ($1, $3, $2);"#,
+VALUES ($1, $2, $3, $4, $5, $6, $7);"#,
         )
-        .bind(row.0)
-        .bind(row.1)
+        .bind(height)
         .bind(row.2)
+        .bind(row.4)
+        .bind(row.3)
+        .bind(header.as_ref().map(|x| x.0.clone()))
+        .bind(header.as_ref().map(|x| x.1.clone()))
+        .bind(header.map(|x| x.2))
         .execute(dbtx.as_mut())
         .await?;
 
+        let transactions: Vec<(i32, String)> =
+            sqlx::query_as("SELECT index, tx_hash FROM tx_results WHERE block_id = $1;")
+                .bind(block_id)
+                .fetch_all(src_db)
+                .await?;
+        for (index, hash) in transactions {
+            sqlx::query(
+                r#"
+INSERT INTO block_transaction
+VALUES ($1, $2, decode($3, 'hex'));"#,
+            )
+            .bind(height)
+            .bind(index)
+            .bind(hash)
+            .execute(dbtx.as_mut())
+            .await?;
+        }
+
         Ok(())
     }
 }
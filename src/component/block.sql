@@ -1,5 +1,26 @@
+-- This component is responsible for processing information about blocks.
+--
+-- The cometbft postgres sink only records the height, chain id, and time of each block,
+-- so the rest of the header is fetched from a cometbft node, if one is configured.
+
 CREATE TABLE IF NOT EXISTS block (
   height BIGINT PRIMARY KEY,
+  chain_id TEXT NOT NULL,
   transaction_count BIGINT NOT NULL,
-  created_at TIMESTAMPTZ NOT NULL
+  created_at TIMESTAMPTZ NOT NULL,
+  -- These are missing if no cometbft node was configured, or it no longer had the block.
+  hash BYTEA,
+  -- The address of the validator which proposed the block.
+  proposer BYTEA,
+  app_hash BYTEA
+);
+
+-- The transactions included in each block, in order.
+CREATE TABLE IF NOT EXISTS block_transaction (
+  height BIGINT NOT NULL,
+  -- The position of this transaction inside of the block.
+  index INTEGER NOT NULL,
+  -- The hash of the transaction.
+  hash BYTEA NOT NULL,
+  PRIMARY KEY (height, index)
 );
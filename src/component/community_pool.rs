@@ -0,0 +1,446 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use axum::async_trait;
+use penumbra_asset::{asset::Id as AssetId, Value, STAKING_TOKEN_ASSET_ID};
+use penumbra_num::Amount;
+use penumbra_proto::{
+    event::ProtoEvent,
+    penumbra::core::{
+        component::{funding::v1 as funding_pb, governance::v1 as gov_pb},
+        transaction::v1 as tx_pb,
+    },
+    Message as _,
+};
+use pindexer::{AppView, ContextualizedEvent, PgTransaction};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sqlx::{
+    postgres::PgRow,
+    types::chrono::{DateTime, Utc},
+    Executor as _, PgPool, Postgres, Transaction,
+};
+
+use super::{action::Action, Context, IndexedTransaction};
+use crate::pagination::Pagination;
+use crate::sql::{SQLAmount, SQLAssetId};
+
+/// The maximum number of flows returned in a single listing.
+const MAX_FLOWS: u64 = 100;
+
+/// The recipient used by funding stream rewards going to the community pool.
+const COMMUNITY_POOL_RECIPIENT: &str = "community-pool";
+
+/// The amount of some asset held by the community pool.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Balance {
+    #[serde_as(as = "DisplayFromStr")]
+    pub asset_id: AssetId,
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for Balance {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (SQLAssetId, SQLAmount) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            asset_id: x.0.into(),
+            amount: x.1.into(),
+        })
+    }
+}
+
+/// A movement of funds in or out of the community pool.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Flow {
+    pub height: i64,
+    /// This is missing for the initial balance at genesis.
+    pub created_at: Option<DateTime<Utc>>,
+    /// One of "genesis", "deposit", "funding", "spend", or "output".
+    pub kind: String,
+    /// Whether or not this flow added funds to the pool.
+    pub inflow: bool,
+    #[serde_as(as = "DisplayFromStr")]
+    pub asset_id: AssetId,
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: Amount,
+    /// The hash of the transaction containing a deposit, in hex.
+    pub tx_hash: Option<String>,
+    /// The proposal approving a spend or output.
+    pub proposal_id: Option<i64>,
+    /// The recipient of an output.
+    pub address: Option<String>,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for Flow {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (
+            i64,
+            Option<DateTime<Utc>>,
+            String,
+            SQLAssetId,
+            SQLAmount,
+            Option<String>,
+            Option<i64>,
+            Option<String>,
+        ) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            height: x.0,
+            created_at: x.1,
+            inflow: !matches!(x.2.as_str(), "spend" | "output"),
+            kind: x.2,
+            asset_id: x.3.into(),
+            amount: x.4.into(),
+            tx_hash: x.5,
+            proposal_id: x.6,
+            address: x.7,
+        })
+    }
+}
+
+/// Record a movement of funds.
+#[allow(clippy::too_many_arguments)]
+async fn insert_flow<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    height: i64,
+    created_at: Option<DateTime<Utc>>,
+    kind: &str,
+    value: Value,
+    tx_hash: Option<&str>,
+    proposal_id: Option<i64>,
+    address: Option<String>,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+INSERT INTO community_pool_flow
+VALUES (DEFAULT, $1, $2, $3, $4, $5, decode($6, 'hex'), $7, $8);"#,
+    )
+    .bind(height)
+    .bind(created_at)
+    .bind(kind)
+    .bind(SQLAssetId::from(value.asset_id))
+    .bind(SQLAmount::from(value.amount))
+    .bind(tx_hash)
+    .bind(proposal_id)
+    .bind(address)
+    .execute(dbtx.as_mut())
+    .await?;
+    Ok(())
+}
+
+/// Index the deposits into the community pool made by a transaction.
+///
+/// Spends and outputs never appear in regular transactions, only in the plans of proposals.
+async fn index_transaction<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    tx: &IndexedTransaction,
+) -> anyhow::Result<()> {
+    for action in tx.actions.iter() {
+        if let Action::CommunityPoolDeposit { asset_id, amount } = action {
+            insert_flow(
+                dbtx,
+                tx.height,
+                Some(tx.created_at),
+                "deposit",
+                Value {
+                    asset_id: *asset_id,
+                    amount: *amount,
+                },
+                Some(&tx.hash),
+                None,
+                None,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Extract the spends and outputs from the transaction plan of a community pool spend proposal.
+///
+/// The chain accepted this proposal, so a plan we can't read only means we can't track its flows.
+fn planned_flows(spend: gov_pb::CommunityPoolSpend) -> Vec<Action> {
+    use tx_pb::{action::Action as A, action_plan::Action as P};
+
+    let plan = spend
+        .transaction_plan
+        .ok_or(anyhow!("community pool spend missing transaction_plan"))
+        .and_then(|x| Ok(tx_pb::TransactionPlan::decode(&x.value[..])?));
+    let plan = match plan {
+        Ok(x) => x,
+        Err(e) => {
+            tracing::warn!(?e, "failed to read community pool spend plan");
+            return Vec::new();
+        }
+    };
+    let mut out = Vec::new();
+    for action in plan.actions.into_iter().filter_map(|x| x.action) {
+        let action = match action {
+            P::CommunityPoolSpend(x) => A::CommunityPoolSpend(x),
+            P::CommunityPoolOutput(x) => A::CommunityPoolOutput(x),
+            _ => continue,
+        };
+        out.push(Action::decode(Some(action)));
+    }
+    out
+}
+
+/// Read the initial balance of the community pool from genesis.
+fn genesis_balance(app_state: &serde_json::Value) -> anyhow::Result<Option<Value>> {
+    let content = match super::genesis_content(app_state)? {
+        Some(x) => x,
+        None => return Ok(None),
+    };
+    content
+        .community_pool_content
+        .and_then(|x| x.initial_balance)
+        .map(Value::try_from)
+        .transpose()
+}
+
+/// One of the possible events that we care about.
+#[derive(Clone, Debug)]
+enum Event {
+    /// A parsed version of [funding_pb::EventFundingStreamReward], going to the community pool.
+    FundingStreamReward { amount: Amount },
+    /// A parsed version of [gov_pb::EventProposalSubmit], for a community pool spend.
+    ProposalSubmit {
+        proposal_id: u64,
+        flows: Vec<Action>,
+    },
+    /// A parsed version of [gov_pb::EventProposalPassed].
+    ProposalPassed { proposal_id: u64 },
+    /// Some other event, which turned out not to concern the community pool.
+    Irrelevant,
+}
+
+impl Event {
+    const NAMES: [&'static str; 3] = [
+        "penumbra.core.component.funding.v1.EventFundingStreamReward",
+        "penumbra.core.component.governance.v1.EventProposalSubmit",
+        "penumbra.core.component.governance.v1.EventProposalPassed",
+    ];
+
+    /// Index this event, using the handle to the postgres transaction.
+    async fn index<'d>(
+        self,
+        dbtx: &mut Transaction<'d, Postgres>,
+        ctx: &Context,
+    ) -> anyhow::Result<()> {
+        match self {
+            Event::FundingStreamReward { amount } => {
+                insert_flow(
+                    dbtx,
+                    ctx.height,
+                    Some(ctx.created_at),
+                    "funding",
+                    Value {
+                        asset_id: *STAKING_TOKEN_ASSET_ID,
+                        amount,
+                    },
+                    None,
+                    None,
+                    None,
+                )
+                .await
+            }
+            Event::ProposalSubmit { proposal_id, flows } => {
+                for flow in flows {
+                    let (kind, asset_id, amount, address) = match flow {
+                        Action::CommunityPoolSpend { asset_id, amount } => {
+                            ("spend", asset_id, amount, None)
+                        }
+                        Action::CommunityPoolOutput {
+                            asset_id,
+                            amount,
+                            address,
+                        } => ("output", asset_id, amount, Some(address.to_string())),
+                        _ => continue,
+                    };
+                    sqlx::query(
+                        r#"
+                    INSERT INTO community_pool_planned_flow
+                    VALUES ($1, $2, $3, $4, $5);
+                    "#,
+                    )
+                    .bind(i64::try_from(proposal_id)?)
+                    .bind(kind)
+                    .bind(SQLAssetId::from(asset_id))
+                    .bind(SQLAmount::from(amount))
+                    .bind(address)
+                    .execute(dbtx.as_mut())
+                    .await?;
+                }
+                Ok(())
+            }
+            Event::ProposalPassed { proposal_id } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO community_pool_flow (height, created_at, kind, asset_id, amount, proposal_id, address)
+                SELECT $2, $3, kind, asset_id, amount, proposal_id, address
+                FROM community_pool_planned_flow
+                WHERE proposal_id = $1;
+                "#,
+                )
+                .bind(i64::try_from(proposal_id)?)
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .execute(dbtx.as_mut())
+                .await?;
+                sqlx::query("DELETE FROM community_pool_planned_flow WHERE proposal_id = $1;")
+                    .bind(i64::try_from(proposal_id)?)
+                    .execute(dbtx.as_mut())
+                    .await?;
+                Ok(())
+            }
+            Event::Irrelevant => Ok(()),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a ContextualizedEvent> for Event {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &'a ContextualizedEvent) -> Result<Self, Self::Error> {
+        match event.event.kind.as_str() {
+            // Funding Stream Reward
+            x if x == Event::NAMES[0] => {
+                let pe = funding_pb::EventFundingStreamReward::from_event(event.as_ref())?;
+                if pe.recipient != COMMUNITY_POOL_RECIPIENT {
+                    return Ok(Self::Irrelevant);
+                }
+                let amount = Amount::try_from(
+                    pe.reward_amount
+                        .ok_or(anyhow!("event missing reward_amount"))?,
+                )?;
+                Ok(Self::FundingStreamReward { amount })
+            }
+            // Proposal Submit
+            x if x == Event::NAMES[1] => {
+                let pe = gov_pb::EventProposalSubmit::from_event(event.as_ref())?;
+                let proposal = pe
+                    .submit
+                    .and_then(|x| x.proposal)
+                    .ok_or(anyhow!("event missing proposal"))?;
+                let spend = match proposal.community_pool_spend {
+                    Some(x) => x,
+                    None => return Ok(Self::Irrelevant),
+                };
+                Ok(Self::ProposalSubmit {
+                    proposal_id: proposal.id,
+                    flows: planned_flows(spend),
+                })
+            }
+            // Proposal Passed
+            x if x == Event::NAMES[2] => {
+                let pe = gov_pb::EventProposalPassed::from_event(event.as_ref())?;
+                let proposal = pe.proposal.ok_or(anyhow!("event missing proposal"))?;
+                Ok(Self::ProposalPassed {
+                    proposal_id: proposal.id,
+                })
+            }
+            x => Err(anyhow!(format!("unrecognized event kind: {x}"))),
+        }
+    }
+}
+
+/// A component for indexing and retrieving the funds of the community pool.
+#[derive(Debug)]
+pub struct Component {
+    event_strings: HashSet<&'static str>,
+}
+
+impl Component {
+    pub const TEMPLATES: [(&'static str, &'static str); 1] = [(
+        "community_pool",
+        include_str!("../../templates/community_pool.html"),
+    )];
+
+    pub fn new() -> Self {
+        let event_strings = Event::NAMES.into_iter().collect();
+        Self { event_strings }
+    }
+
+    pub fn attach_to_indexer(self, indexer: pindexer::Indexer) -> pindexer::Indexer {
+        indexer.with_index(self)
+    }
+
+    /// Fetch the current balance of each asset in the community pool, largest first.
+    pub async fn balances(pool: &PgPool) -> anyhow::Result<Vec<Balance>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT asset_id, SUM(CASE WHEN kind = 'spend' THEN -amount ELSE amount END) AS balance
+FROM community_pool_flow
+WHERE kind <> 'output'
+GROUP BY asset_id
+HAVING SUM(CASE WHEN kind = 'spend' THEN -amount ELSE amount END) > 0
+ORDER BY balance DESC;"#,
+        )
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch the movements of funds, in a given range of heights.
+    ///
+    /// This will be sorted in reverse order, with at most [MAX_FLOWS] being returned.
+    pub async fn flows(pool: &PgPool, pagination: &Pagination<i64>) -> anyhow::Result<Vec<Flow>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT height, created_at, kind, asset_id, amount, encode(tx_hash, 'hex'), proposal_id, address
+FROM community_pool_flow
+WHERE height BETWEEN $1 AND $2
+ORDER BY id DESC
+LIMIT $3;"#,
+        )
+        .bind(pagination.start)
+        .bind(pagination.stop)
+        .bind(i64::try_from(pagination.limit(MAX_FLOWS))?)
+        .fetch_all(pool)
+        .await?)
+    }
+}
+
+#[async_trait]
+impl AppView for Component {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        dbtx.as_mut()
+            .execute(include_str!("community_pool.sql"))
+            .await?;
+        // Not knowing the initial balance shouldn't prevent us from indexing anything else.
+        match genesis_balance(app_state) {
+            Ok(Some(value)) => {
+                insert_flow(dbtx, 0, None, "genesis", value, None, None, None).await?;
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(?e, "failed to read community pool balance from genesis"),
+        }
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        type_str == "tx" || self.event_strings.contains(type_str)
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        if event.event.kind == "tx" {
+            return match IndexedTransaction::fetch(src_db, event).await? {
+                Some(tx) if tx.succeeded() => index_transaction(dbtx, &tx).await,
+                _ => Ok(()),
+            };
+        }
+        let ctx = Context::fetch(src_db, event).await?;
+        Event::try_from(event)?.index(dbtx, &ctx).await
+    }
+}
@@ -0,0 +1,42 @@
+-- This component is responsible for tracking the funds moving in and out of the community pool.
+
+-- Each movement of funds in or out of the community pool.
+CREATE TABLE IF NOT EXISTS community_pool_flow (
+  id BIGSERIAL PRIMARY KEY,
+  -- This is 0 for the initial balance at genesis.
+  height BIGINT NOT NULL,
+  -- This is missing for the initial balance at genesis.
+  created_at TIMESTAMPTZ,
+  -- One of:
+  -- - "genesis", the initial balance,
+  -- - "deposit", a deposit made in a transaction,
+  -- - "funding", the contribution of a funding stream at the end of an epoch,
+  -- - "spend", funds taken out by a passed proposal,
+  -- - "output", a note created with those funds.
+  --
+  -- Outputs are only there for information: the funds have already been counted by the spend.
+  kind TEXT NOT NULL,
+  asset_id BYTEA NOT NULL,
+  amount NUMERIC(39, 0) NOT NULL,
+  -- The transaction containing a deposit.
+  tx_hash BYTEA,
+  -- The proposal approving a spend or output.
+  proposal_id BIGINT,
+  -- The recipient of an output.
+  address TEXT
+);
+
+CREATE INDEX IF NOT EXISTS community_pool_flow_height_idx ON community_pool_flow (height);
+
+-- The spends and outputs of community pool spend proposals which haven't passed (yet).
+CREATE TABLE IF NOT EXISTS community_pool_planned_flow (
+  proposal_id BIGINT NOT NULL,
+  -- Either "spend" or "output".
+  kind TEXT NOT NULL,
+  asset_id BYTEA NOT NULL,
+  amount NUMERIC(39, 0) NOT NULL,
+  address TEXT
+);
+
+CREATE INDEX IF NOT EXISTS community_pool_planned_flow_proposal_idx
+  ON community_pool_planned_flow (proposal_id);
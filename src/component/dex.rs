@@ -1,17 +1,483 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt::Display, str::FromStr};
 
 use anyhow::anyhow;
 use axum::async_trait;
-use penumbra_asset::asset::Id as AssetId;
+use penumbra_asset::{asset::Id as AssetId, Value};
+use penumbra_dex::{lp::position, TradingPair};
 use penumbra_num::Amount;
 use penumbra_proto::{event::ProtoEvent, penumbra::core::component::dex::v1 as pb};
 use pindexer::{AppView, ContextualizedEvent, PgTransaction};
-use sqlx::{PgPool, Postgres, Transaction};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sqlx::{
+    postgres::PgRow,
+    types::chrono::{DateTime, Utc},
+    Executor as _, PgPool, Postgres, Transaction,
+};
 
-use crate::sql::{SQLAmount, SQLAssetId};
+use super::{net_flow, Context};
+use crate::pagination::Pagination;
+use crate::sql::{SQLAmount, SQLAssetId, SQLPositionId};
+
+/// The maximum number of trades returned in a single listing.
+const MAX_TRADES: u64 = 100;
+/// The maximum number of arbs returned in a single listing.
+const MAX_ARBS: u64 = 100;
+/// The maximum number of candles returned in a single listing.
+const MAX_CANDLES: i64 = 1000;
+/// The maximum number of batch swaps returned when looking at the activity of an asset.
+const MAX_ASSET_ACTIVITY: i64 = 20;
+
+/// The amount of a given asset held by the DEX.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetBalance {
+    #[serde_as(as = "DisplayFromStr")]
+    pub asset_id: AssetId,
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for AssetBalance {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (SQLAssetId, SQLAmount) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            asset_id: x.0.into(),
+            amount: x.1.into(),
+        })
+    }
+}
+
+/// The amount of a given asset held by the DEX, at the end of some span of blocks.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValuePoint {
+    /// The last height with a change in this bucket.
+    pub height: i64,
+    /// The time of the last change in this bucket.
+    pub time: DateTime<Utc>,
+    /// The amount held at the end of this bucket, i.e. after that last change.
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for ValuePoint {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (i64, DateTime<Utc>, SQLAmount) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            height: x.0,
+            time: x.1,
+            amount: x.2.into(),
+        })
+    }
+}
+
+/// How to group changes together when building a history.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Bucket {
+    /// Group changes into spans of a fixed number of blocks.
+    Blocks(i64),
+    Hour,
+    #[default]
+    Day,
+}
+
+impl FromStr for Bucket {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hour" => Ok(Self::Hour),
+            "day" => Ok(Self::Day),
+            x => match x.parse::<i64>() {
+                Ok(blocks) if blocks > 0 => Ok(Self::Blocks(blocks)),
+                _ => Err(anyhow!("invalid bucket: {x}")),
+            },
+        }
+    }
+}
+
+impl Display for Bucket {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Blocks(blocks) => write!(f, "{blocks}"),
+            Self::Hour => write!(f, "hour"),
+            Self::Day => write!(f, "day"),
+        }
+    }
+}
+
+/// The current state of a liquidity position.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Position {
+    #[serde_as(as = "DisplayFromStr")]
+    pub position_id: position::Id,
+    #[serde_as(as = "DisplayFromStr")]
+    pub asset_1: AssetId,
+    #[serde_as(as = "DisplayFromStr")]
+    pub asset_2: AssetId,
+    #[serde_as(as = "DisplayFromStr")]
+    pub reserves_1: Amount,
+    #[serde_as(as = "DisplayFromStr")]
+    pub reserves_2: Amount,
+    /// The fee charged by the position, in basis points.
+    pub fee_bps: i32,
+    /// One of "opened", "closed", or "withdrawn".
+    pub state: String,
+    pub opened_height: i64,
+    pub opened_at: DateTime<Utc>,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for Position {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (
+            SQLPositionId,
+            SQLAssetId,
+            SQLAssetId,
+            SQLAmount,
+            SQLAmount,
+            i32,
+            String,
+            i64,
+            DateTime<Utc>,
+        ) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            position_id: x.0.into(),
+            asset_1: x.1.into(),
+            asset_2: x.2.into(),
+            reserves_1: x.3.into(),
+            reserves_2: x.4.into(),
+            fee_bps: x.5,
+            state: x.6,
+            opened_height: x.7,
+            opened_at: x.8,
+        })
+    }
+}
+
+/// Something which happened to a liquidity position.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionEvent {
+    pub height: i64,
+    pub created_at: DateTime<Utc>,
+    /// One of "open", "execution", "close", or "withdraw".
+    pub kind: String,
+    /// The reserves after this event, or the reserves withdrawn, for a withdrawal.
+    #[serde_as(as = "DisplayFromStr")]
+    pub reserves_1: Amount,
+    #[serde_as(as = "DisplayFromStr")]
+    pub reserves_2: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for PositionEvent {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (i64, DateTime<Utc>, String, SQLAmount, SQLAmount) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            height: x.0,
+            created_at: x.1,
+            kind: x.2,
+            reserves_1: x.3.into(),
+            reserves_2: x.4.into(),
+        })
+    }
+}
+
+/// The execution of a batch of swaps, from the point of view of a base and quote asset.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trade {
+    pub height: i64,
+    pub created_at: DateTime<Utc>,
+    /// The number of swaps in this batch.
+    pub swap_count: i64,
+    /// The amount of the base asset sold.
+    #[serde_as(as = "DisplayFromStr")]
+    pub base_in: Amount,
+    /// The amount of the quote asset received for the base asset sold.
+    #[serde_as(as = "DisplayFromStr")]
+    pub quote_out: Amount,
+    /// The amount of the quote asset sold.
+    #[serde_as(as = "DisplayFromStr")]
+    pub quote_in: Amount,
+    /// The amount of the base asset received for the quote asset sold.
+    #[serde_as(as = "DisplayFromStr")]
+    pub base_out: Amount,
+    /// The price of the base asset, in terms of the quote asset, if anything traded.
+    pub price: Option<f64>,
+}
+
+impl Trade {
+    /// Create a trade from the raw amounts, with 1 being the base and 2 the quote asset.
+    fn new(
+        height: i64,
+        created_at: DateTime<Utc>,
+        swap_count: i64,
+        [delta_1, delta_2, lambda_1, lambda_2, unfilled_1, unfilled_2]: [Amount; 6],
+    ) -> anyhow::Result<Self> {
+        let base_in = delta_1.checked_sub(&unfilled_1).unwrap_or_default();
+        let quote_in = delta_2.checked_sub(&unfilled_2).unwrap_or_default();
+        let base_traded = base_in
+            .checked_add(&lambda_1)
+            .ok_or(anyhow!("base amount traded overflowed"))?
+            .value();
+        let quote_traded = quote_in
+            .checked_add(&lambda_2)
+            .ok_or(anyhow!("quote amount traded overflowed"))?
+            .value();
+        let price = if base_traded == 0 || quote_traded == 0 {
+            None
+        } else {
+            Some(quote_traded as f64 / base_traded as f64)
+        };
+        Ok(Self {
+            height,
+            created_at,
+            swap_count,
+            base_in,
+            quote_out: lambda_2,
+            quote_in,
+            base_out: lambda_1,
+            price,
+        })
+    }
+}
+
+/// The length of time summarized by a single candlestick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CandleWindow {
+    OneMinute,
+    FifteenMinutes,
+    #[default]
+    OneHour,
+    OneDay,
+}
+
+impl CandleWindow {
+    /// Every window, each of which gets its own candles when indexing.
+    const ALL: [Self; 4] = [
+        Self::OneMinute,
+        Self::FifteenMinutes,
+        Self::OneHour,
+        Self::OneDay,
+    ];
+
+    fn seconds(self) -> i64 {
+        match self {
+            Self::OneMinute => 60,
+            Self::FifteenMinutes => 15 * 60,
+            Self::OneHour => 60 * 60,
+            Self::OneDay => 24 * 60 * 60,
+        }
+    }
+
+    /// The start of the window containing a given time.
+    fn start_of(self, time: DateTime<Utc>) -> anyhow::Result<DateTime<Utc>> {
+        let seconds = time.timestamp() - time.timestamp().rem_euclid(self.seconds());
+        DateTime::from_timestamp(seconds, 0).ok_or(anyhow!("invalid timestamp: {}", seconds))
+    }
+}
+
+impl FromStr for CandleWindow {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1m" => Ok(Self::OneMinute),
+            "15m" => Ok(Self::FifteenMinutes),
+            "1h" => Ok(Self::OneHour),
+            "1d" => Ok(Self::OneDay),
+            x => Err(anyhow!("invalid window: {x}")),
+        }
+    }
+}
+
+impl Display for CandleWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::OneMinute => write!(f, "1m"),
+            Self::FifteenMinutes => write!(f, "15m"),
+            Self::OneHour => write!(f, "1h"),
+            Self::OneDay => write!(f, "1d"),
+        }
+    }
+}
+
+/// A summary of the prices and volume of a pair over a window of time.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Candle {
+    pub start_time: DateTime<Utc>,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    /// The amount of the base asset traded.
+    #[serde_as(as = "DisplayFromStr")]
+    pub base_volume: Amount,
+    /// The amount of the quote asset traded.
+    #[serde_as(as = "DisplayFromStr")]
+    pub quote_volume: Amount,
+}
+
+/// An amount of a given asset, at one step of an execution trace.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceValue {
+    #[serde_as(as = "DisplayFromStr")]
+    pub asset_id: AssetId,
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: Amount,
+}
+
+impl From<Value> for TraceValue {
+    fn from(value: Value) -> Self {
+        Self {
+            asset_id: value.asset_id,
+            amount: value.amount,
+        }
+    }
+}
+
+/// The arbitrage executed by the DEX in a given block.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Arb {
+    pub height: i64,
+    pub created_at: DateTime<Utc>,
+    pub input: TraceValue,
+    pub output: TraceValue,
+    /// The amount gained, if the input and output are the same asset.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub profit: Option<Amount>,
+    /// The path of each trade making up the arbitrage.
+    pub traces: Vec<Vec<TraceValue>>,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for Arb {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (
+            i64,
+            DateTime<Utc>,
+            SQLAssetId,
+            SQLAmount,
+            SQLAssetId,
+            SQLAmount,
+            &'r str,
+        ) = sqlx::FromRow::from_row(row)?;
+        let input = TraceValue {
+            asset_id: x.2.into(),
+            amount: x.3.into(),
+        };
+        let output = TraceValue {
+            asset_id: x.4.into(),
+            amount: x.5.into(),
+        };
+        let profit = if input.asset_id == output.asset_id {
+            output.amount.checked_sub(&input.amount)
+        } else {
+            None
+        };
+        let traces = serde_json::from_str(x.6).map_err(|e| sqlx::Error::ColumnDecode {
+            index: "6".to_string(),
+            source: Box::new(e),
+        })?;
+        Ok(Self {
+            height: x.0,
+            created_at: x.1,
+            input,
+            output,
+            profit,
+            traces,
+        })
+    }
+}
+
+/// The total arbitrage profit captured in a given asset.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbProfit {
+    #[serde_as(as = "DisplayFromStr")]
+    pub asset_id: AssetId,
+    /// Whether the arbitrage produced less than it consumed.
+    pub negative: bool,
+    #[serde_as(as = "DisplayFromStr")]
+    pub profit: Amount,
+    /// The number of blocks with arbitrage in this asset.
+    pub arb_count: i64,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for ArbProfit {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (SQLAssetId, SQLAmount, SQLAmount, i64) = sqlx::FromRow::from_row(row)?;
+        let (positive, profit) = net_flow(x.2.into(), x.1.into());
+        Ok(Self {
+            asset_id: x.0.into(),
+            negative: !positive,
+            profit,
+            arb_count: x.3,
+        })
+    }
+}
+
+/// A trading pair involving a given asset, seen from the point of view of that asset.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetPair {
+    /// The other asset in the pair.
+    #[serde_as(as = "DisplayFromStr")]
+    pub counterpart: AssetId,
+    /// The number of blocks with a batch swap for this pair.
+    pub batch_count: i64,
+    /// The last height with a batch swap for this pair.
+    pub last_height: i64,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for AssetPair {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (SQLAssetId, i64, i64) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            counterpart: x.0.into(),
+            batch_count: x.1,
+            last_height: x.2,
+        })
+    }
+}
+
+/// A batch swap involving a given asset, seen from the point of view of that asset.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetActivity {
+    pub height: i64,
+    pub created_at: DateTime<Utc>,
+    /// The other asset in the pair.
+    #[serde_as(as = "DisplayFromStr")]
+    pub counterpart: AssetId,
+    /// The amount of this asset being swapped.
+    #[serde_as(as = "DisplayFromStr")]
+    pub swapped_in: Amount,
+    /// The amount of this asset output by the swaps.
+    #[serde_as(as = "DisplayFromStr")]
+    pub swapped_out: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for AssetActivity {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (i64, DateTime<Utc>, SQLAssetId, SQLAmount, SQLAmount) =
+            sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            height: x.0,
+            created_at: x.1,
+            counterpart: x.2.into(),
+            swapped_in: x.3.into(),
+            swapped_out: x.4.into(),
+        })
+    }
+}
 
 /// One of the possible events that we care about.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 enum Event {
     /// A parsed version of [pb::EventValueCircuitBreakerCredit].
     CircuitBreakerCredit {
@@ -25,17 +491,102 @@ enum Event {
         previous_balance: Amount,
         new_balance: Amount,
     },
+    /// A parsed version of [pb::EventPositionOpen].
+    PositionOpen {
+        position_id: position::Id,
+        trading_pair: TradingPair,
+        reserves_1: Amount,
+        reserves_2: Amount,
+        fee: u32,
+    },
+    /// A parsed version of [pb::EventPositionExecution].
+    PositionExecution {
+        position_id: position::Id,
+        reserves_1: Amount,
+        reserves_2: Amount,
+    },
+    /// A parsed version of [pb::EventPositionClose].
+    PositionClose { position_id: position::Id },
+    /// A parsed version of [pb::EventPositionWithdraw].
+    PositionWithdraw {
+        position_id: position::Id,
+        reserves_1: Amount,
+        reserves_2: Amount,
+    },
+    /// A parsed version of [pb::EventSwap].
+    Swap {
+        trading_pair: TradingPair,
+        delta_1: Amount,
+        delta_2: Amount,
+    },
+    /// A parsed version of [pb::EventSwapClaim].
+    SwapClaim { trading_pair: TradingPair },
+    /// A parsed version of [pb::EventBatchSwap].
+    BatchSwap {
+        trading_pair: TradingPair,
+        delta_1: Amount,
+        delta_2: Amount,
+        lambda_1: Amount,
+        lambda_2: Amount,
+        unfilled_1: Amount,
+        unfilled_2: Amount,
+    },
+    /// A parsed version of [pb::EventArbExecution].
+    ArbExecution {
+        input: Value,
+        output: Value,
+        traces: Vec<Vec<Value>>,
+    },
+}
+
+/// Record something happening to a position.
+async fn insert_position_event<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    ctx: &Context,
+    position_id: position::Id,
+    kind: &str,
+    reserves_1: Amount,
+    reserves_2: Amount,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+    INSERT INTO dex_position_event
+    VALUES ($1, $2, $3, $4, $5, $6, $7);
+    "#,
+    )
+    .bind(ctx.rowid)
+    .bind(SQLPositionId::from(position_id))
+    .bind(ctx.height)
+    .bind(ctx.created_at)
+    .bind(kind)
+    .bind(SQLAmount::from(reserves_1))
+    .bind(SQLAmount::from(reserves_2))
+    .execute(dbtx.as_mut())
+    .await?;
+    Ok(())
 }
 
 impl Event {
-    const NAMES: [&'static str; 2] = [
+    const NAMES: [&'static str; 10] = [
         "penumbra.core.component.dex.v1.EventValueCircuitBreakerCredit",
         "penumbra.core.component.dex.v1.EventValueCircuitBreakerDebit",
+        "penumbra.core.component.dex.v1.EventPositionOpen",
+        "penumbra.core.component.dex.v1.EventPositionExecution",
+        "penumbra.core.component.dex.v1.EventPositionClose",
+        "penumbra.core.component.dex.v1.EventPositionWithdraw",
+        "penumbra.core.component.dex.v1.EventSwap",
+        "penumbra.core.component.dex.v1.EventSwapClaim",
+        "penumbra.core.component.dex.v1.EventBatchSwap",
+        "penumbra.core.component.dex.v1.EventArbExecution",
     ];
 
     /// Index this event, using the handle to the postgres transaction.
-    async fn index<'d>(&self, dbtx: &mut Transaction<'d, Postgres>) -> anyhow::Result<()> {
-        match *self {
+    async fn index<'d>(
+        self,
+        dbtx: &mut Transaction<'d, Postgres>,
+        ctx: &Context,
+    ) -> anyhow::Result<()> {
+        match self {
             Event::CircuitBreakerCredit {
                 asset_id,
                 previous_balance,
@@ -49,9 +600,12 @@ impl Event {
                 sqlx::query(
                     r#"
                 INSERT INTO dex_value_circuit_breaker_change
-                VALUES ($1, $2);
+                VALUES ($1, $2, $3, $4, $5);
                 "#,
                 )
+                .bind(ctx.rowid)
+                .bind(ctx.height)
+                .bind(ctx.created_at)
                 .bind(SQLAssetId::from(asset_id))
                 .bind(SQLAmount::from(amount))
                 .execute(dbtx.as_mut())
@@ -71,15 +625,249 @@ impl Event {
                 sqlx::query(
                     r#"
                 INSERT INTO dex_value_circuit_breaker_change
-                VALUES ($1, -$2);
+                VALUES ($1, $2, $3, $4, -$5);
                 "#,
                 )
+                .bind(ctx.rowid)
+                .bind(ctx.height)
+                .bind(ctx.created_at)
                 .bind(SQLAssetId::from(asset_id))
                 .bind(SQLAmount::from(amount))
                 .execute(dbtx.as_mut())
                 .await?;
                 Ok(())
             }
+            Event::PositionOpen {
+                position_id,
+                trading_pair,
+                reserves_1,
+                reserves_2,
+                fee,
+            } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO dex_position
+                VALUES ($1, $2, $3, $4, $5, $6, 'opened', $7, $8);
+                "#,
+                )
+                .bind(SQLPositionId::from(position_id))
+                .bind(SQLAssetId::from(trading_pair.asset_1()))
+                .bind(SQLAssetId::from(trading_pair.asset_2()))
+                .bind(SQLAmount::from(reserves_1))
+                .bind(SQLAmount::from(reserves_2))
+                .bind(i32::try_from(fee)?)
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .execute(dbtx.as_mut())
+                .await?;
+                insert_position_event(dbtx, ctx, position_id, "open", reserves_1, reserves_2).await
+            }
+            Event::PositionExecution {
+                position_id,
+                reserves_1,
+                reserves_2,
+            } => {
+                let result = sqlx::query(
+                    r#"
+                UPDATE dex_position
+                SET reserves_1 = $2, reserves_2 = $3
+                WHERE position_id = $1;
+                "#,
+                )
+                .bind(SQLPositionId::from(position_id))
+                .bind(SQLAmount::from(reserves_1))
+                .bind(SQLAmount::from(reserves_2))
+                .execute(dbtx.as_mut())
+                .await?;
+                // Positions we never saw opened have nothing to update.
+                if result.rows_affected() == 0 {
+                    return Ok(());
+                }
+                insert_position_event(dbtx, ctx, position_id, "execution", reserves_1, reserves_2)
+                    .await
+            }
+            Event::PositionClose { position_id } => {
+                let reserves: Option<(SQLAmount, SQLAmount)> = sqlx::query_as(
+                    r#"
+                UPDATE dex_position
+                SET state = 'closed'
+                WHERE position_id = $1
+                RETURNING reserves_1, reserves_2;
+                "#,
+                )
+                .bind(SQLPositionId::from(position_id))
+                .fetch_optional(dbtx.as_mut())
+                .await?;
+                let (reserves_1, reserves_2) = match reserves {
+                    None => return Ok(()),
+                    Some(x) => x,
+                };
+                insert_position_event(
+                    dbtx,
+                    ctx,
+                    position_id,
+                    "close",
+                    reserves_1.into(),
+                    reserves_2.into(),
+                )
+                .await
+            }
+            Event::PositionWithdraw {
+                position_id,
+                reserves_1,
+                reserves_2,
+            } => {
+                let result = sqlx::query(
+                    r#"
+                UPDATE dex_position
+                SET state = 'withdrawn', reserves_1 = 0, reserves_2 = 0
+                WHERE position_id = $1;
+                "#,
+                )
+                .bind(SQLPositionId::from(position_id))
+                .execute(dbtx.as_mut())
+                .await?;
+                if result.rows_affected() == 0 {
+                    return Ok(());
+                }
+                insert_position_event(dbtx, ctx, position_id, "withdraw", reserves_1, reserves_2)
+                    .await
+            }
+            Event::Swap {
+                trading_pair,
+                delta_1,
+                delta_2,
+            } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO dex_swap
+                VALUES ($1, $2, $3, $4, $5, $6, $7);
+                "#,
+                )
+                .bind(ctx.rowid)
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .bind(SQLAssetId::from(trading_pair.asset_1()))
+                .bind(SQLAssetId::from(trading_pair.asset_2()))
+                .bind(SQLAmount::from(delta_1))
+                .bind(SQLAmount::from(delta_2))
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::SwapClaim { trading_pair } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO dex_swap_claim
+                VALUES ($1, $2, $3, $4, $5);
+                "#,
+                )
+                .bind(ctx.rowid)
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .bind(SQLAssetId::from(trading_pair.asset_1()))
+                .bind(SQLAssetId::from(trading_pair.asset_2()))
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::BatchSwap {
+                trading_pair,
+                delta_1,
+                delta_2,
+                lambda_1,
+                lambda_2,
+                unfilled_1,
+                unfilled_2,
+            } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO dex_batch_swap
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10);
+                "#,
+                )
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .bind(SQLAssetId::from(trading_pair.asset_1()))
+                .bind(SQLAssetId::from(trading_pair.asset_2()))
+                .bind(SQLAmount::from(delta_1))
+                .bind(SQLAmount::from(delta_2))
+                .bind(SQLAmount::from(lambda_1))
+                .bind(SQLAmount::from(lambda_2))
+                .bind(SQLAmount::from(unfilled_1))
+                .bind(SQLAmount::from(unfilled_2))
+                .execute(dbtx.as_mut())
+                .await?;
+
+                let trade = Trade::new(
+                    ctx.height,
+                    ctx.created_at,
+                    0,
+                    [delta_1, delta_2, lambda_1, lambda_2, unfilled_1, unfilled_2],
+                )?;
+                let price = match trade.price {
+                    None => return Ok(()),
+                    Some(x) => x,
+                };
+                let base_volume = trade
+                    .base_in
+                    .checked_add(&trade.base_out)
+                    .ok_or(anyhow!("base volume overflowed"))?;
+                let quote_volume = trade
+                    .quote_in
+                    .checked_add(&trade.quote_out)
+                    .ok_or(anyhow!("quote volume overflowed"))?;
+                for window in CandleWindow::ALL {
+                    sqlx::query(
+                        r#"
+                    INSERT INTO dex_candle
+                    VALUES ($1, $2, $3, $4, $5, $5, $5, $5, $6, $7)
+                    ON CONFLICT (asset_1, asset_2, duration, start_time) DO UPDATE SET
+                        high = GREATEST(dex_candle.high, EXCLUDED.high),
+                        low = LEAST(dex_candle.low, EXCLUDED.low),
+                        close = EXCLUDED.close,
+                        volume_1 = dex_candle.volume_1 + EXCLUDED.volume_1,
+                        volume_2 = dex_candle.volume_2 + EXCLUDED.volume_2;
+                    "#,
+                    )
+                    .bind(SQLAssetId::from(trading_pair.asset_1()))
+                    .bind(SQLAssetId::from(trading_pair.asset_2()))
+                    .bind(window.to_string())
+                    .bind(window.start_of(ctx.created_at)?)
+                    .bind(price)
+                    .bind(SQLAmount::from(base_volume))
+                    .bind(SQLAmount::from(quote_volume))
+                    .execute(dbtx.as_mut())
+                    .await?;
+                }
+                Ok(())
+            }
+            Event::ArbExecution {
+                input,
+                output,
+                traces,
+            } => {
+                let traces: Vec<Vec<TraceValue>> = traces
+                    .into_iter()
+                    .map(|x| x.into_iter().map(TraceValue::from).collect())
+                    .collect();
+                sqlx::query(
+                    r#"
+                INSERT INTO dex_arb
+                VALUES ($1, $2, $3, $4, $5, $6, $7);
+                "#,
+                )
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .bind(SQLAssetId::from(input.asset_id))
+                .bind(SQLAmount::from(input.amount))
+                .bind(SQLAssetId::from(output.asset_id))
+                .bind(SQLAmount::from(output.amount))
+                .bind(serde_json::to_string(&traces)?)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
         }
     }
 }
@@ -123,6 +911,149 @@ impl<'a> TryFrom<&'a ContextualizedEvent> for Event {
                     new_balance,
                 })
             }
+            // Position Open
+            x if x == Event::NAMES[2] => {
+                let pe = pb::EventPositionOpen::from_event(event.as_ref())?;
+                let position_id = position::Id::try_from(
+                    pe.position_id.ok_or(anyhow!("event missing position_id"))?,
+                )?;
+                let trading_pair = TradingPair::try_from(
+                    pe.trading_pair
+                        .ok_or(anyhow!("event missing trading_pair"))?,
+                )?;
+                let reserves_1 =
+                    Amount::try_from(pe.reserves_1.ok_or(anyhow!("event missing reserves_1"))?)?;
+                let reserves_2 =
+                    Amount::try_from(pe.reserves_2.ok_or(anyhow!("event missing reserves_2"))?)?;
+                Ok(Self::PositionOpen {
+                    position_id,
+                    trading_pair,
+                    reserves_1,
+                    reserves_2,
+                    fee: pe.trading_fee,
+                })
+            }
+            // Position Execution
+            x if x == Event::NAMES[3] => {
+                let pe = pb::EventPositionExecution::from_event(event.as_ref())?;
+                let position_id = position::Id::try_from(
+                    pe.position_id.ok_or(anyhow!("event missing position_id"))?,
+                )?;
+                let reserves_1 =
+                    Amount::try_from(pe.reserves_1.ok_or(anyhow!("event missing reserves_1"))?)?;
+                let reserves_2 =
+                    Amount::try_from(pe.reserves_2.ok_or(anyhow!("event missing reserves_2"))?)?;
+                Ok(Self::PositionExecution {
+                    position_id,
+                    reserves_1,
+                    reserves_2,
+                })
+            }
+            // Position Close
+            x if x == Event::NAMES[4] => {
+                let pe = pb::EventPositionClose::from_event(event.as_ref())?;
+                let position_id = position::Id::try_from(
+                    pe.position_id.ok_or(anyhow!("event missing position_id"))?,
+                )?;
+                Ok(Self::PositionClose { position_id })
+            }
+            // Position Withdraw
+            x if x == Event::NAMES[5] => {
+                let pe = pb::EventPositionWithdraw::from_event(event.as_ref())?;
+                let position_id = position::Id::try_from(
+                    pe.position_id.ok_or(anyhow!("event missing position_id"))?,
+                )?;
+                let reserves_1 =
+                    Amount::try_from(pe.reserves_1.ok_or(anyhow!("event missing reserves_1"))?)?;
+                let reserves_2 =
+                    Amount::try_from(pe.reserves_2.ok_or(anyhow!("event missing reserves_2"))?)?;
+                Ok(Self::PositionWithdraw {
+                    position_id,
+                    reserves_1,
+                    reserves_2,
+                })
+            }
+            // Swap
+            x if x == Event::NAMES[6] => {
+                let pe = pb::EventSwap::from_event(event.as_ref())?;
+                let trading_pair = TradingPair::try_from(
+                    pe.trading_pair
+                        .ok_or(anyhow!("event missing trading_pair"))?,
+                )?;
+                let delta_1 =
+                    Amount::try_from(pe.delta_1_i.ok_or(anyhow!("event missing delta_1_i"))?)?;
+                let delta_2 =
+                    Amount::try_from(pe.delta_2_i.ok_or(anyhow!("event missing delta_2_i"))?)?;
+                Ok(Self::Swap {
+                    trading_pair,
+                    delta_1,
+                    delta_2,
+                })
+            }
+            // Swap Claim
+            x if x == Event::NAMES[7] => {
+                let pe = pb::EventSwapClaim::from_event(event.as_ref())?;
+                let trading_pair = TradingPair::try_from(
+                    pe.trading_pair
+                        .ok_or(anyhow!("event missing trading_pair"))?,
+                )?;
+                Ok(Self::SwapClaim { trading_pair })
+            }
+            // Batch Swap
+            x if x == Event::NAMES[8] => {
+                let pe = pb::EventBatchSwap::from_event(event.as_ref())?;
+                let output = pe
+                    .batch_swap_output_data
+                    .ok_or(anyhow!("event missing batch_swap_output_data"))?;
+                let trading_pair = TradingPair::try_from(
+                    output
+                        .trading_pair
+                        .ok_or(anyhow!("event missing trading_pair"))?,
+                )?;
+                let amount = |x: Option<penumbra_proto::penumbra::core::num::v1::Amount>,
+                              name: &str|
+                 -> anyhow::Result<Amount> {
+                    Ok(Amount::try_from(
+                        x.ok_or(anyhow!("event missing {}", name))?,
+                    )?)
+                };
+                Ok(Self::BatchSwap {
+                    trading_pair,
+                    delta_1: amount(output.delta_1, "delta_1")?,
+                    delta_2: amount(output.delta_2, "delta_2")?,
+                    lambda_1: amount(output.lambda_1, "lambda_1")?,
+                    lambda_2: amount(output.lambda_2, "lambda_2")?,
+                    unfilled_1: amount(output.unfilled_1, "unfilled_1")?,
+                    unfilled_2: amount(output.unfilled_2, "unfilled_2")?,
+                })
+            }
+            // Arb Execution
+            x if x == Event::NAMES[9] => {
+                let pe = pb::EventArbExecution::from_event(event.as_ref())?;
+                let execution = pe
+                    .swap_execution
+                    .ok_or(anyhow!("event missing swap_execution"))?;
+                let input =
+                    Value::try_from(execution.input.ok_or(anyhow!("event missing input"))?)?;
+                let output =
+                    Value::try_from(execution.output.ok_or(anyhow!("event missing output"))?)?;
+                let traces = execution
+                    .traces
+                    .into_iter()
+                    .map(|trace| {
+                        trace
+                            .value
+                            .into_iter()
+                            .map(Value::try_from)
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(Self::ArbExecution {
+                    input,
+                    output,
+                    traces,
+                })
+            }
             x => Err(anyhow!(format!("unrecognized event kind: {x}"))),
         }
     }
@@ -134,6 +1065,31 @@ pub struct Component {
 }
 
 impl Component {
+    pub const TEMPLATES: [(&'static str, &'static str); 7] = [
+        ("dex_value", include_str!("../../templates/dex_value.html")),
+        (
+            "dex_value_history",
+            include_str!("../../templates/dex_value_history.html"),
+        ),
+        (
+            "dex_positions",
+            include_str!("../../templates/dex_positions.html"),
+        ),
+        (
+            "dex_position",
+            include_str!("../../templates/dex_position.html"),
+        ),
+        (
+            "dex_trades",
+            include_str!("../../templates/dex_trades.html"),
+        ),
+        (
+            "dex_candles",
+            include_str!("../../templates/dex_candles.html"),
+        ),
+        ("dex_arbs", include_str!("../../templates/dex_arbs.html")),
+    ];
+
     pub fn new() -> Self {
         let event_strings = Event::NAMES.into_iter().collect();
         Self { event_strings }
@@ -142,6 +1098,319 @@ impl Component {
     pub fn attach_to_indexer(self, indexer: pindexer::Indexer) -> pindexer::Indexer {
         indexer.with_index(self)
     }
+
+    /// Fetch the current amount of each asset held by the DEX, in descending order.
+    pub async fn value(pool: &PgPool) -> anyhow::Result<Vec<AssetBalance>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT asset_id, SUM(flow) AS balance
+FROM dex_value_circuit_breaker_change
+GROUP BY asset_id
+HAVING SUM(flow) > 0
+ORDER BY balance DESC;"#,
+        )
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch the current amount of a given asset held by the DEX.
+    pub async fn asset_value(pool: &PgPool, asset_id: AssetId) -> anyhow::Result<Amount> {
+        let (amount,): (SQLAmount,) = sqlx::query_as(
+            r#"
+SELECT COALESCE(SUM(flow), 0)
+FROM dex_value_circuit_breaker_change
+WHERE asset_id = $1;"#,
+        )
+        .bind(SQLAssetId::from(asset_id))
+        .fetch_one(pool)
+        .await?;
+        Ok(amount.into())
+    }
+
+    /// Fetch the trading pairs a given asset has been swapped in, most recently traded first.
+    pub async fn asset_pairs(pool: &PgPool, asset_id: AssetId) -> anyhow::Result<Vec<AssetPair>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    CASE WHEN asset_1 = $1 THEN asset_2 ELSE asset_1 END AS counterpart,
+    COUNT(*),
+    MAX(height) AS last_height
+FROM dex_batch_swap
+WHERE asset_1 = $1 OR asset_2 = $1
+GROUP BY counterpart
+ORDER BY last_height DESC;"#,
+        )
+        .bind(SQLAssetId::from(asset_id))
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch the most recent batch swaps involving a given asset.
+    ///
+    /// This will be sorted in reverse order, with at most [MAX_ASSET_ACTIVITY] being returned.
+    pub async fn asset_activity(
+        pool: &PgPool,
+        asset_id: AssetId,
+    ) -> anyhow::Result<Vec<AssetActivity>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    height,
+    created_at,
+    CASE WHEN asset_1 = $1 THEN asset_2 ELSE asset_1 END,
+    CASE WHEN asset_1 = $1 THEN delta_1 ELSE delta_2 END,
+    CASE WHEN asset_1 = $1 THEN lambda_1 ELSE lambda_2 END
+FROM dex_batch_swap
+WHERE asset_1 = $1 OR asset_2 = $1
+ORDER BY height DESC
+LIMIT $2;"#,
+        )
+        .bind(SQLAssetId::from(asset_id))
+        .bind(MAX_ASSET_ACTIVITY)
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch the amount of a given asset held by the DEX over time.
+    ///
+    /// Each point contains the amount held at the end of its bucket,
+    /// and buckets without any changes are skipped.
+    pub async fn value_history(
+        pool: &PgPool,
+        asset_id: AssetId,
+        bucket: Bucket,
+    ) -> anyhow::Result<Vec<ValuePoint>> {
+        let query = match bucket {
+            Bucket::Blocks(blocks) => sqlx::query_as(
+                r#"
+SELECT height, time, SUM(flow) OVER (ORDER BY bucket)
+FROM (
+    SELECT height / $2 AS bucket, MAX(height) AS height, MAX(created_at) AS time, SUM(flow) AS flow
+    FROM dex_value_circuit_breaker_change
+    WHERE asset_id = $1
+    GROUP BY bucket
+) AS buckets
+ORDER BY bucket;"#,
+            )
+            .bind(SQLAssetId::from(asset_id))
+            .bind(blocks),
+            Bucket::Hour | Bucket::Day => sqlx::query_as(
+                r#"
+SELECT height, time, SUM(flow) OVER (ORDER BY bucket)
+FROM (
+    SELECT date_trunc($2, created_at) AS bucket, MAX(height) AS height, MAX(created_at) AS time, SUM(flow) AS flow
+    FROM dex_value_circuit_breaker_change
+    WHERE asset_id = $1
+    GROUP BY bucket
+) AS buckets
+ORDER BY bucket;"#,
+            )
+            .bind(SQLAssetId::from(asset_id))
+            .bind(bucket.to_string()),
+        };
+        Ok(query.fetch_all(pool).await?)
+    }
+
+    /// Fetch the open liquidity positions, optionally restricted to a given trading pair.
+    ///
+    /// These are sorted by trading pair, and then by the time they were opened.
+    pub async fn positions(
+        pool: &PgPool,
+        trading_pair: Option<TradingPair>,
+    ) -> anyhow::Result<Vec<Position>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT position_id, asset_1, asset_2, reserves_1, reserves_2, fee_bps, state, opened_height, opened_at
+FROM dex_position
+WHERE state = 'opened'
+AND ($1::BYTEA IS NULL OR (asset_1 = $1 AND asset_2 = $2))
+ORDER BY asset_1, asset_2, opened_height DESC;"#,
+        )
+        .bind(trading_pair.map(|x| SQLAssetId::from(x.asset_1())))
+        .bind(trading_pair.map(|x| SQLAssetId::from(x.asset_2())))
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch a specific liquidity position, by its identifier.
+    pub async fn position(pool: &PgPool, position_id: position::Id) -> anyhow::Result<Position> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT position_id, asset_1, asset_2, reserves_1, reserves_2, fee_bps, state, opened_height, opened_at
+FROM dex_position
+WHERE position_id = $1;"#,
+        )
+        .bind(SQLPositionId::from(position_id))
+        .fetch_one(pool)
+        .await?)
+    }
+
+    /// Fetch the executed batch swaps for a pair, from the point of view of a base asset.
+    ///
+    /// This will be sorted in reverse order, with at most [MAX_TRADES] being returned.
+    pub async fn trades(
+        pool: &PgPool,
+        base: AssetId,
+        quote: AssetId,
+        pagination: &Pagination<i64>,
+    ) -> anyhow::Result<Vec<Trade>> {
+        let trading_pair = TradingPair::new(base, quote);
+        let rows: Vec<(
+            i64,
+            DateTime<Utc>,
+            i64,
+            SQLAmount,
+            SQLAmount,
+            SQLAmount,
+            SQLAmount,
+            SQLAmount,
+            SQLAmount,
+        )> = sqlx::query_as(
+            r#"
+SELECT
+    height,
+    created_at,
+    (SELECT count(*) FROM dex_swap
+     WHERE dex_swap.asset_1 = $1 AND dex_swap.asset_2 = $2 AND dex_swap.height = dex_batch_swap.height),
+    delta_1, delta_2, lambda_1, lambda_2, unfilled_1, unfilled_2
+FROM dex_batch_swap
+WHERE asset_1 = $1 AND asset_2 = $2 AND height BETWEEN $3 AND $4
+ORDER BY height DESC
+LIMIT $5;"#,
+        )
+        .bind(SQLAssetId::from(trading_pair.asset_1()))
+        .bind(SQLAssetId::from(trading_pair.asset_2()))
+        .bind(pagination.start)
+        .bind(pagination.stop)
+        .bind(i64::try_from(pagination.limit(MAX_TRADES))?)
+        .fetch_all(pool)
+        .await?;
+        let base_is_1 = base == trading_pair.asset_1();
+        rows.into_iter()
+            .map(|x| {
+                let (d1, d2, l1, l2, u1, u2) = (
+                    Amount::from(x.3),
+                    Amount::from(x.4),
+                    Amount::from(x.5),
+                    Amount::from(x.6),
+                    Amount::from(x.7),
+                    Amount::from(x.8),
+                );
+                let amounts = if base_is_1 {
+                    [d1, d2, l1, l2, u1, u2]
+                } else {
+                    [d2, d1, l2, l1, u2, u1]
+                };
+                Trade::new(x.0, x.1, x.2, amounts)
+            })
+            .collect()
+    }
+
+    /// Fetch the most recent candles for a pair, from the point of view of a base asset.
+    ///
+    /// These are sorted in chronological order, with at most [MAX_CANDLES] being returned.
+    pub async fn candles(
+        pool: &PgPool,
+        base: AssetId,
+        quote: AssetId,
+        window: CandleWindow,
+    ) -> anyhow::Result<Vec<Candle>> {
+        let trading_pair = TradingPair::new(base, quote);
+        let rows: Vec<(DateTime<Utc>, f64, f64, f64, f64, SQLAmount, SQLAmount)> = sqlx::query_as(
+            r#"
+SELECT start_time, open, high, low, close, volume_1, volume_2
+FROM dex_candle
+WHERE asset_1 = $1 AND asset_2 = $2 AND duration = $3
+ORDER BY start_time DESC
+LIMIT $4;"#,
+        )
+        .bind(SQLAssetId::from(trading_pair.asset_1()))
+        .bind(SQLAssetId::from(trading_pair.asset_2()))
+        .bind(window.to_string())
+        .bind(MAX_CANDLES)
+        .fetch_all(pool)
+        .await?;
+        // Candles are stored with the first asset as the base, so we need to invert
+        // the prices when the base is the second asset.
+        let base_is_1 = base == trading_pair.asset_1();
+        Ok(rows
+            .into_iter()
+            .rev()
+            .map(|(start_time, open, high, low, close, volume_1, volume_2)| {
+                if base_is_1 {
+                    Candle {
+                        start_time,
+                        open,
+                        high,
+                        low,
+                        close,
+                        base_volume: volume_1.into(),
+                        quote_volume: volume_2.into(),
+                    }
+                } else {
+                    Candle {
+                        start_time,
+                        open: 1.0 / open,
+                        high: 1.0 / low,
+                        low: 1.0 / high,
+                        close: 1.0 / close,
+                        base_volume: volume_2.into(),
+                        quote_volume: volume_1.into(),
+                    }
+                }
+            })
+            .collect())
+    }
+
+    /// Fetch the arbitrage executed in a range of blocks.
+    ///
+    /// This will be sorted in reverse order, with at most [MAX_ARBS] being returned.
+    pub async fn arbs(pool: &PgPool, pagination: &Pagination<i64>) -> anyhow::Result<Vec<Arb>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT height, created_at, input_asset_id, input_amount, output_asset_id, output_amount, traces
+FROM dex_arb
+WHERE height BETWEEN $1 AND $2
+ORDER BY height DESC
+LIMIT $3;"#,
+        )
+        .bind(pagination.start)
+        .bind(pagination.stop)
+        .bind(i64::try_from(pagination.limit(MAX_ARBS))?)
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch the total arbitrage profit captured in each asset, in descending order.
+    pub async fn arb_profits(pool: &PgPool) -> anyhow::Result<Vec<ArbProfit>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT input_asset_id, SUM(input_amount), SUM(output_amount), COUNT(*)
+FROM dex_arb
+WHERE input_asset_id = output_asset_id
+GROUP BY input_asset_id
+ORDER BY SUM(output_amount) - SUM(input_amount) DESC;"#,
+        )
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch everything that has happened to a liquidity position, in order.
+    pub async fn position_history(
+        pool: &PgPool,
+        position_id: position::Id,
+    ) -> anyhow::Result<Vec<PositionEvent>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT height, created_at, kind, reserves_1, reserves_2
+FROM dex_position_event
+WHERE position_id = $1
+ORDER BY rowid;"#,
+        )
+        .bind(SQLPositionId::from(position_id))
+        .fetch_all(pool)
+        .await?)
+    }
 }
 
 #[async_trait]
@@ -151,9 +1420,7 @@ impl AppView for Component {
         dbtx: &mut PgTransaction,
         _app_state: &serde_json::Value,
     ) -> anyhow::Result<()> {
-        sqlx::query(include_str!("dex.sql"))
-            .execute(dbtx.as_mut())
-            .await?;
+        dbtx.as_mut().execute(include_str!("dex.sql")).await?;
         Ok(())
     }
 
@@ -166,8 +1433,9 @@ impl AppView for Component {
         &self,
         dbtx: &mut PgTransaction,
         event: &ContextualizedEvent,
-        _src_db: &PgPool,
+        src_db: &PgPool,
     ) -> anyhow::Result<()> {
-        Event::try_from(event)?.index(dbtx).await
+        let ctx = Context::fetch(src_db, event).await?;
+        Event::try_from(event)?.index(dbtx, &ctx).await
     }
 }
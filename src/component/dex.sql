@@ -11,6 +11,12 @@
 
 -- Keeps track of changes to the dex's value circuit breaker.
 CREATE TABLE IF NOT EXISTS dex_value_circuit_breaker_change (
+  -- The rowid of the event in the source database, which gives us a total ordering.
+  rowid BIGINT PRIMARY KEY,
+  -- The height at which this change happened.
+  height BIGINT NOT NULL,
+  -- The time of the block containing this change.
+  created_at TIMESTAMPTZ NOT NULL,
   -- The asset being moved into or out of the dex.
   asset_id BYTEA NOT NULL,
   -- The flow, either positive, or negative, into the dex via this particular asset.
@@ -18,3 +24,122 @@ CREATE TABLE IF NOT EXISTS dex_value_circuit_breaker_change (
   -- Because we're dealing with arbitrary assets, we need to use something which can store u128
   flow NUMERIC(39, 0) NOT NULL
 );
+
+CREATE INDEX IF NOT EXISTS dex_value_circuit_breaker_change_asset_idx
+  ON dex_value_circuit_breaker_change (asset_id, height);
+
+-- The current state of each liquidity position.
+CREATE TABLE IF NOT EXISTS dex_position (
+  position_id BYTEA PRIMARY KEY,
+  -- The assets of the trading pair, in canonical order.
+  asset_1 BYTEA NOT NULL,
+  asset_2 BYTEA NOT NULL,
+  -- The current reserves of each asset.
+  reserves_1 NUMERIC(39, 0) NOT NULL,
+  reserves_2 NUMERIC(39, 0) NOT NULL,
+  -- The fee charged by the position, in basis points.
+  fee_bps INTEGER NOT NULL,
+  -- One of "opened", "closed", or "withdrawn".
+  state TEXT NOT NULL,
+  opened_height BIGINT NOT NULL,
+  opened_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS dex_position_pair_idx ON dex_position (asset_1, asset_2, state);
+
+-- Every event in the lifecycle of a position.
+CREATE TABLE IF NOT EXISTS dex_position_event (
+  -- The rowid of the event in the source database, which gives us a total ordering.
+  rowid BIGINT PRIMARY KEY,
+  position_id BYTEA NOT NULL,
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  -- One of "open", "execution", "close", or "withdraw".
+  kind TEXT NOT NULL,
+  -- The reserves after this event, or the reserves withdrawn, for a withdrawal.
+  reserves_1 NUMERIC(39, 0) NOT NULL,
+  reserves_2 NUMERIC(39, 0) NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS dex_position_event_position_idx ON dex_position_event (position_id);
+
+-- Each swap submitted to the dex.
+CREATE TABLE IF NOT EXISTS dex_swap (
+  -- The rowid of the event in the source database, which gives us a total ordering.
+  rowid BIGINT PRIMARY KEY,
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  -- The assets of the trading pair, in canonical order.
+  asset_1 BYTEA NOT NULL,
+  asset_2 BYTEA NOT NULL,
+  -- The amount of each asset being swapped.
+  delta_1 NUMERIC(39, 0) NOT NULL,
+  delta_2 NUMERIC(39, 0) NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS dex_swap_pair_idx ON dex_swap (asset_1, asset_2, height);
+
+-- Each claim of the outputs of a swap.
+CREATE TABLE IF NOT EXISTS dex_swap_claim (
+  rowid BIGINT PRIMARY KEY,
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  asset_1 BYTEA NOT NULL,
+  asset_2 BYTEA NOT NULL
+);
+
+-- The result of executing all of the swaps for a trading pair in a given block.
+CREATE TABLE IF NOT EXISTS dex_batch_swap (
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  asset_1 BYTEA NOT NULL,
+  asset_2 BYTEA NOT NULL,
+  -- The total amount of each asset being swapped.
+  delta_1 NUMERIC(39, 0) NOT NULL,
+  delta_2 NUMERIC(39, 0) NOT NULL,
+  -- The total amount of each asset output by the swaps.
+  lambda_1 NUMERIC(39, 0) NOT NULL,
+  lambda_2 NUMERIC(39, 0) NOT NULL,
+  -- The amount of each input asset which could not be swapped, and was returned.
+  unfilled_1 NUMERIC(39, 0) NOT NULL,
+  unfilled_2 NUMERIC(39, 0) NOT NULL,
+  PRIMARY KEY (asset_1, asset_2, height)
+);
+
+-- The primary key already covers lookups by the first asset, for the activity of an asset.
+CREATE INDEX IF NOT EXISTS dex_batch_swap_asset_2_idx ON dex_batch_swap (asset_2, height);
+
+-- Candlesticks summarizing the batch swaps of each trading pair over a window of time.
+--
+-- Prices are for the first asset, in terms of the second asset, and are
+-- updated as each batch swap is indexed, rather than being computed on demand.
+CREATE TABLE IF NOT EXISTS dex_candle (
+  asset_1 BYTEA NOT NULL,
+  asset_2 BYTEA NOT NULL,
+  -- The length of the window, e.g. "15m".
+  duration TEXT NOT NULL,
+  -- The start of the window.
+  start_time TIMESTAMPTZ NOT NULL,
+  open DOUBLE PRECISION NOT NULL,
+  high DOUBLE PRECISION NOT NULL,
+  low DOUBLE PRECISION NOT NULL,
+  close DOUBLE PRECISION NOT NULL,
+  -- The amount of each asset traded.
+  volume_1 NUMERIC(39, 0) NOT NULL,
+  volume_2 NUMERIC(39, 0) NOT NULL,
+  PRIMARY KEY (asset_1, asset_2, duration, start_time)
+);
+
+-- The arbitrage executed by the dex at the end of each block.
+CREATE TABLE IF NOT EXISTS dex_arb (
+  height BIGINT PRIMARY KEY,
+  created_at TIMESTAMPTZ NOT NULL,
+  -- The asset and amount put into the arbitrage.
+  input_asset_id BYTEA NOT NULL,
+  input_amount NUMERIC(39, 0) NOT NULL,
+  -- The asset and amount coming out of the arbitrage.
+  output_asset_id BYTEA NOT NULL,
+  output_amount NUMERIC(39, 0) NOT NULL,
+  -- A JSON array of traces, each of which is an array of objects with an `asset_id` and `amount`.
+  traces TEXT NOT NULL
+);
@@ -0,0 +1,303 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use axum::async_trait;
+use penumbra_num::Amount;
+use penumbra_proto::{
+    event::ProtoEvent,
+    penumbra::core::component::{funding::v1 as funding_pb, sct::v1 as sct_pb},
+};
+use pindexer::{AppView, ContextualizedEvent, PgTransaction};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sqlx::{
+    postgres::PgRow,
+    types::chrono::{DateTime, Utc},
+    Executor as _, PgPool, Postgres, Transaction,
+};
+
+use super::Context;
+use crate::pagination::Pagination;
+use crate::sql::SQLAmount;
+
+/// The maximum number of epochs returned in a single listing.
+const MAX_EPOCHS: u64 = 100;
+
+/// A summary of an epoch.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Epoch {
+    pub index: i64,
+    pub start_height: i64,
+    /// This is missing for the current epoch.
+    pub end_height: Option<i64>,
+    /// This is missing if we haven't indexed the first block of the epoch.
+    pub start_time: Option<DateTime<Utc>>,
+    /// The number of blocks indexed in this epoch so far.
+    pub block_count: i64,
+    /// The number of transactions in this epoch so far.
+    pub transaction_count: i64,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for Epoch {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (i64, i64, Option<i64>, Option<DateTime<Utc>>, i64, i64) =
+            sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            index: x.0,
+            start_height: x.1,
+            end_height: x.2,
+            start_time: x.3,
+            block_count: x.4,
+            transaction_count: x.5,
+        })
+    }
+}
+
+/// The reward issued to a funding stream at the end of an epoch.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpochReward {
+    pub recipient: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for EpochReward {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (String, SQLAmount) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            recipient: x.0,
+            amount: x.1.into(),
+        })
+    }
+}
+
+/// One of the possible events that we care about.
+#[derive(Clone, Debug)]
+enum Event {
+    /// A parsed version of [sct_pb::EventEpochRoot].
+    EpochRoot { index: u64 },
+    /// A parsed version of [funding_pb::EventFundingStreamReward].
+    FundingStreamReward {
+        recipient: String,
+        epoch_index: u64,
+        amount: Amount,
+    },
+}
+
+impl Event {
+    const NAMES: [&'static str; 2] = [
+        "penumbra.core.component.sct.v1.EventEpochRoot",
+        "penumbra.core.component.funding.v1.EventFundingStreamReward",
+    ];
+
+    /// Index this event, using the handle to the postgres transaction.
+    async fn index<'d>(
+        self,
+        dbtx: &mut Transaction<'d, Postgres>,
+        ctx: &Context,
+    ) -> anyhow::Result<()> {
+        match self {
+            Event::EpochRoot { index } => {
+                let index = i64::try_from(index)?;
+                sqlx::query(
+                    r#"
+                UPDATE epoch
+                SET end_height = $2
+                WHERE index = $1;
+                "#,
+                )
+                .bind(index)
+                .bind(ctx.height)
+                .execute(dbtx.as_mut())
+                .await?;
+                sqlx::query(
+                    r#"
+                INSERT INTO epoch
+                VALUES ($1, $2, NULL)
+                ON CONFLICT (index) DO NOTHING;
+                "#,
+                )
+                .bind(index + 1)
+                .bind(ctx.height + 1)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::FundingStreamReward {
+                recipient,
+                epoch_index,
+                amount,
+            } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO epoch_reward
+                VALUES ($1, $2, $3, $4, $5);
+                "#,
+                )
+                .bind(ctx.rowid)
+                .bind(i64::try_from(epoch_index)?)
+                .bind(ctx.height)
+                .bind(recipient)
+                .bind(SQLAmount::from(amount))
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a ContextualizedEvent> for Event {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &'a ContextualizedEvent) -> Result<Self, Self::Error> {
+        match event.event.kind.as_str() {
+            // Epoch Root
+            x if x == Event::NAMES[0] => {
+                let pe = sct_pb::EventEpochRoot::from_event(event.as_ref())?;
+                Ok(Self::EpochRoot { index: pe.index })
+            }
+            // Funding Stream Reward
+            x if x == Event::NAMES[1] => {
+                let pe = funding_pb::EventFundingStreamReward::from_event(event.as_ref())?;
+                let amount = Amount::try_from(
+                    pe.reward_amount
+                        .ok_or(anyhow!("event missing reward_amount"))?,
+                )?;
+                Ok(Self::FundingStreamReward {
+                    recipient: pe.recipient,
+                    epoch_index: pe.epoch_index,
+                    amount,
+                })
+            }
+            x => Err(anyhow!(format!("unrecognized event kind: {x}"))),
+        }
+    }
+}
+
+/// A component for indexing and retrieving information about epochs.
+#[derive(Debug)]
+pub struct Component {
+    event_strings: HashSet<&'static str>,
+}
+
+impl Component {
+    pub const TEMPLATES: [(&'static str, &'static str); 2] = [
+        ("epochs", include_str!("../../templates/epochs.html")),
+        ("epoch", include_str!("../../templates/epoch.html")),
+    ];
+
+    pub fn new() -> Self {
+        let event_strings = Event::NAMES.into_iter().collect();
+        Self { event_strings }
+    }
+
+    pub fn attach_to_indexer(self, indexer: pindexer::Indexer) -> pindexer::Indexer {
+        indexer.with_index(self)
+    }
+
+    /// Fetch a list of epochs, in a given range of epoch indices.
+    ///
+    /// This will be sorted in reverse order, with at most [MAX_EPOCHS] being returned.
+    pub async fn epochs(pool: &PgPool, pagination: &Pagination<i64>) -> anyhow::Result<Vec<Epoch>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    epoch.index,
+    epoch.start_height,
+    epoch.end_height,
+    start_block.created_at,
+    stats.block_count,
+    stats.transaction_count
+FROM epoch
+LEFT JOIN block AS start_block ON start_block.height = epoch.start_height
+CROSS JOIN LATERAL (
+    SELECT COUNT(*) AS block_count, COALESCE(SUM(transaction_count), 0)::BIGINT AS transaction_count
+    FROM block
+    WHERE height >= epoch.start_height AND (epoch.end_height IS NULL OR height <= epoch.end_height)
+) AS stats
+WHERE epoch.index BETWEEN $1 AND $2
+ORDER BY epoch.index DESC
+LIMIT $3;"#,
+        )
+        .bind(pagination.start)
+        .bind(pagination.stop)
+        .bind(i64::try_from(pagination.limit(MAX_EPOCHS))?)
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch a specific epoch, by its index.
+    pub async fn epoch(pool: &PgPool, index: i64) -> anyhow::Result<Epoch> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    epoch.index,
+    epoch.start_height,
+    epoch.end_height,
+    start_block.created_at,
+    stats.block_count,
+    stats.transaction_count
+FROM epoch
+LEFT JOIN block AS start_block ON start_block.height = epoch.start_height
+CROSS JOIN LATERAL (
+    SELECT COUNT(*) AS block_count, COALESCE(SUM(transaction_count), 0)::BIGINT AS transaction_count
+    FROM block
+    WHERE height >= epoch.start_height AND (epoch.end_height IS NULL OR height <= epoch.end_height)
+) AS stats
+WHERE epoch.index = $1;"#,
+        )
+        .bind(index)
+        .fetch_one(pool)
+        .await?)
+    }
+
+    /// Fetch the rewards issued to funding streams for an epoch, in descending order.
+    pub async fn rewards(pool: &PgPool, index: i64) -> anyhow::Result<Vec<EpochReward>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT recipient, SUM(amount) AS amount
+FROM epoch_reward
+WHERE epoch_index = $1
+GROUP BY recipient
+ORDER BY amount DESC;"#,
+        )
+        .bind(index)
+        .fetch_all(pool)
+        .await?)
+    }
+}
+
+#[async_trait]
+impl AppView for Component {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        dbtx.as_mut().execute(include_str!("epoch.sql")).await?;
+        // A chain starting from genesis starts in the first epoch, at the first height.
+        if let Ok(Some(_)) = super::genesis_content(app_state) {
+            sqlx::query("INSERT INTO epoch VALUES (0, 1, NULL) ON CONFLICT (index) DO NOTHING;")
+                .execute(dbtx.as_mut())
+                .await?;
+        }
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        self.event_strings.contains(type_str)
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        let ctx = Context::fetch(src_db, event).await?;
+        Event::try_from(event)?.index(dbtx, &ctx).await
+    }
+}
@@ -0,0 +1,28 @@
+-- This component is responsible for tracking epochs.
+--
+-- The end of each epoch is marked by the commitment tree emitting the root of that epoch,
+-- with the next epoch starting at the following height. When starting from a checkpoint,
+-- epochs are only known from the first boundary onwards.
+
+CREATE TABLE IF NOT EXISTS epoch (
+  index BIGINT PRIMARY KEY,
+  start_height BIGINT NOT NULL,
+  -- This is missing for the current epoch.
+  end_height BIGINT
+);
+
+-- The rewards issued to funding streams at the end of each epoch.
+--
+-- This isn't the whole issuance of an epoch: delegators are rewarded separately,
+-- through the exchange rate of their delegation tokens.
+CREATE TABLE IF NOT EXISTS epoch_reward (
+  -- The rowid of the event in the source database, which gives us a total ordering.
+  rowid BIGINT PRIMARY KEY,
+  epoch_index BIGINT NOT NULL,
+  height BIGINT NOT NULL,
+  -- The recipient of the reward, e.g. an address.
+  recipient TEXT NOT NULL,
+  amount NUMERIC(39, 0) NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS epoch_reward_epoch_idx ON epoch_reward (epoch_index);
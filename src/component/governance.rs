@@ -0,0 +1,640 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use axum::async_trait;
+use decaf377_rdsa::VerificationKeyBytes;
+use penumbra_num::Amount;
+use penumbra_proto::{event::ProtoEvent, penumbra::core::component::governance::v1 as pb};
+use penumbra_stake::IdentityKey;
+use pindexer::{AppView, ContextualizedEvent, PgTransaction};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sqlx::{
+    postgres::PgRow,
+    types::chrono::{DateTime, Utc},
+    Executor as _, PgPool, Postgres, Transaction,
+};
+
+use super::{action::vote_name, Context};
+use crate::pagination::Pagination;
+use crate::sql::SQLAmount;
+
+/// The maximum number of proposals returned in a single listing.
+const MAX_PROPOSALS: u64 = 100;
+
+/// The amount of voting power behind each option, so far.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tally {
+    #[serde_as(as = "DisplayFromStr")]
+    pub yes: Amount,
+    #[serde_as(as = "DisplayFromStr")]
+    pub no: Amount,
+    #[serde_as(as = "DisplayFromStr")]
+    pub abstain: Amount,
+}
+
+/// A governance proposal, along with its current tally.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proposal {
+    pub id: i64,
+    pub title: String,
+    pub description: String,
+    /// The kind of proposal, e.g. "parameter_change".
+    pub kind: String,
+    /// The contents specific to this kind of proposal, as pretty-printed JSON.
+    pub payload: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub deposit_amount: Amount,
+    pub start_height: i64,
+    pub end_height: i64,
+    pub submitted_at: DateTime<Utc>,
+    /// One of "voting", "withdrawn", "passed", "failed", or "slashed".
+    pub state: String,
+    pub withdraw_reason: Option<String>,
+    pub outcome_height: Option<i64>,
+    pub deposit_claimed: bool,
+    pub tally: Tally,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for Proposal {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (
+            i64,
+            String,
+            String,
+            String,
+            String,
+            SQLAmount,
+            i64,
+            i64,
+            DateTime<Utc>,
+            String,
+            Option<String>,
+            Option<i64>,
+            bool,
+            SQLAmount,
+            SQLAmount,
+            SQLAmount,
+        ) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            id: x.0,
+            title: x.1,
+            description: x.2,
+            kind: x.3,
+            payload: x.4,
+            deposit_amount: x.5.into(),
+            start_height: x.6,
+            end_height: x.7,
+            submitted_at: x.8,
+            state: x.9,
+            withdraw_reason: x.10,
+            outcome_height: x.11,
+            deposit_claimed: x.12,
+            tally: Tally {
+                yes: x.13.into(),
+                no: x.14.into(),
+                abstain: x.15.into(),
+            },
+        })
+    }
+}
+
+/// The vote of a validator on a proposal.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidatorVote {
+    #[serde_as(as = "DisplayFromStr")]
+    pub identity: IdentityKey,
+    /// The name of the validator, if it still exists.
+    pub name: Option<String>,
+    pub vote: String,
+    pub voting_power: i64,
+    pub height: i64,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for ValidatorVote {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: ([u8; 32], Option<String>, String, i64, i64) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            identity: IdentityKey(VerificationKeyBytes::from(x.0)),
+            name: x.1,
+            vote: x.2,
+            voting_power: x.3,
+            height: x.4,
+        })
+    }
+}
+
+/// The votes of the delegators of a validator on a proposal, for a given option.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegatorVotes {
+    #[serde_as(as = "DisplayFromStr")]
+    pub identity: IdentityKey,
+    /// The name of the validator, if it still exists.
+    pub name: Option<String>,
+    pub vote: String,
+    /// The number of votes cast.
+    pub count: i64,
+    /// The total amount of the staking token behind these votes.
+    #[serde_as(as = "DisplayFromStr")]
+    pub unbonded_amount: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for DelegatorVotes {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: ([u8; 32], Option<String>, String, i64, SQLAmount) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            identity: IdentityKey(VerificationKeyBytes::from(x.0)),
+            name: x.1,
+            vote: x.2,
+            count: x.3,
+            unbonded_amount: x.4.into(),
+        })
+    }
+}
+
+/// How a validator voted on a proposal it could have voted on.
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
+pub struct VotingRecord {
+    pub proposal_id: i64,
+    pub title: String,
+    /// The state of the proposal, e.g. "passed".
+    pub state: String,
+    /// This will be missing if the validator did not vote.
+    pub vote: Option<String>,
+    pub height: Option<i64>,
+}
+
+/// The kind of a proposal, and its kind specific contents, as pretty-printed JSON.
+fn proposal_payload(proposal: &pb::Proposal) -> anyhow::Result<(&'static str, String)> {
+    Ok(if let Some(x) = &proposal.signaling {
+        ("signaling", serde_json::to_string_pretty(x)?)
+    } else if let Some(x) = &proposal.emergency {
+        ("emergency", serde_json::to_string_pretty(x)?)
+    } else if let Some(x) = &proposal.parameter_change {
+        ("parameter_change", serde_json::to_string_pretty(x)?)
+    } else if let Some(x) = &proposal.community_pool_spend {
+        ("community_pool_spend", serde_json::to_string_pretty(x)?)
+    } else if let Some(x) = &proposal.upgrade_plan {
+        ("upgrade_plan", serde_json::to_string_pretty(x)?)
+    } else if let Some(x) = &proposal.freeze_ibc_client {
+        ("freeze_ibc_client", serde_json::to_string_pretty(x)?)
+    } else if let Some(x) = &proposal.unfreeze_ibc_client {
+        ("unfreeze_ibc_client", serde_json::to_string_pretty(x)?)
+    } else {
+        return Err(anyhow!("proposal {} missing payload", proposal.id));
+    })
+}
+
+/// Record the outcome of a proposal.
+async fn set_outcome<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    ctx: &Context,
+    proposal_id: u64,
+    state: &str,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+    UPDATE governance_proposal
+    SET state = $2, outcome_height = $3
+    WHERE id = $1;
+    "#,
+    )
+    .bind(i64::try_from(proposal_id)?)
+    .bind(state)
+    .bind(ctx.height)
+    .execute(dbtx.as_mut())
+    .await?;
+    Ok(())
+}
+
+/// One of the possible events that we care about.
+#[derive(Clone, Debug)]
+enum Event {
+    /// A parsed version of [pb::EventProposalSubmit].
+    ProposalSubmit {
+        proposal: pb::Proposal,
+        deposit_amount: Amount,
+        start_height: u64,
+        end_height: u64,
+    },
+    /// A parsed version of [pb::EventValidatorVote].
+    ValidatorVote {
+        proposal_id: u64,
+        identity_key: IdentityKey,
+        vote: String,
+        voting_power: u64,
+    },
+    /// A parsed version of [pb::EventDelegatorVote].
+    DelegatorVote {
+        proposal_id: u64,
+        identity_key: IdentityKey,
+        vote: String,
+        unbonded_amount: Amount,
+    },
+    /// A parsed version of [pb::EventProposalWithdraw].
+    ProposalWithdraw { proposal_id: u64, reason: String },
+    /// A parsed version of [pb::EventProposalPassed].
+    ProposalPassed { proposal_id: u64 },
+    /// A parsed version of [pb::EventProposalFailed].
+    ProposalFailed { proposal_id: u64 },
+    /// A parsed version of [pb::EventProposalSlashed].
+    ProposalSlashed { proposal_id: u64 },
+    /// A parsed version of [pb::EventProposalDepositClaim].
+    ProposalDepositClaim { proposal_id: u64 },
+}
+
+impl Event {
+    const NAMES: [&'static str; 8] = [
+        "penumbra.core.component.governance.v1.EventProposalSubmit",
+        "penumbra.core.component.governance.v1.EventValidatorVote",
+        "penumbra.core.component.governance.v1.EventDelegatorVote",
+        "penumbra.core.component.governance.v1.EventProposalWithdraw",
+        "penumbra.core.component.governance.v1.EventProposalPassed",
+        "penumbra.core.component.governance.v1.EventProposalFailed",
+        "penumbra.core.component.governance.v1.EventProposalSlashed",
+        "penumbra.core.component.governance.v1.EventProposalDepositClaim",
+    ];
+
+    /// Index this event, using the handle to the postgres transaction.
+    async fn index<'d>(
+        self,
+        dbtx: &mut Transaction<'d, Postgres>,
+        ctx: &Context,
+    ) -> anyhow::Result<()> {
+        match self {
+            Event::ProposalSubmit {
+                proposal,
+                deposit_amount,
+                start_height,
+                end_height,
+            } => {
+                let (kind, payload) = proposal_payload(&proposal)?;
+                sqlx::query(
+                    r#"
+                INSERT INTO governance_proposal
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, 'voting', NULL, NULL, FALSE);
+                "#,
+                )
+                .bind(i64::try_from(proposal.id)?)
+                .bind(&proposal.title)
+                .bind(&proposal.description)
+                .bind(kind)
+                .bind(payload)
+                .bind(SQLAmount::from(deposit_amount))
+                .bind(i64::try_from(start_height)?)
+                .bind(i64::try_from(end_height)?)
+                .bind(ctx.created_at)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::ValidatorVote {
+                proposal_id,
+                identity_key,
+                vote,
+                voting_power,
+            } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO governance_validator_vote
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (proposal_id, ik) DO UPDATE SET
+                    vote = EXCLUDED.vote,
+                    voting_power = EXCLUDED.voting_power,
+                    height = EXCLUDED.height,
+                    created_at = EXCLUDED.created_at;
+                "#,
+                )
+                .bind(i64::try_from(proposal_id)?)
+                .bind(identity_key.to_bytes())
+                .bind(vote)
+                .bind(i64::try_from(voting_power)?)
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::DelegatorVote {
+                proposal_id,
+                identity_key,
+                vote,
+                unbonded_amount,
+            } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO governance_delegator_vote
+                VALUES ($1, $2, $3, $4, $5, $6, $7);
+                "#,
+                )
+                .bind(ctx.rowid)
+                .bind(i64::try_from(proposal_id)?)
+                .bind(identity_key.to_bytes())
+                .bind(vote)
+                .bind(SQLAmount::from(unbonded_amount))
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::ProposalWithdraw {
+                proposal_id,
+                reason,
+            } => {
+                sqlx::query(
+                    r#"
+                UPDATE governance_proposal
+                SET state = 'withdrawn', withdraw_reason = $2
+                WHERE id = $1;
+                "#,
+                )
+                .bind(i64::try_from(proposal_id)?)
+                .bind(reason)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::ProposalPassed { proposal_id } => {
+                set_outcome(dbtx, ctx, proposal_id, "passed").await
+            }
+            Event::ProposalFailed { proposal_id } => {
+                set_outcome(dbtx, ctx, proposal_id, "failed").await
+            }
+            Event::ProposalSlashed { proposal_id } => {
+                set_outcome(dbtx, ctx, proposal_id, "slashed").await
+            }
+            Event::ProposalDepositClaim { proposal_id } => {
+                sqlx::query(
+                    r#"
+                UPDATE governance_proposal
+                SET deposit_claimed = TRUE
+                WHERE id = $1;
+                "#,
+                )
+                .bind(i64::try_from(proposal_id)?)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a ContextualizedEvent> for Event {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &'a ContextualizedEvent) -> Result<Self, Self::Error> {
+        match event.event.kind.as_str() {
+            // Proposal Submit
+            x if x == Event::NAMES[0] => {
+                let pe = pb::EventProposalSubmit::from_event(event.as_ref())?;
+                let submit = pe.submit.ok_or(anyhow!("event missing submit"))?;
+                let proposal = submit.proposal.ok_or(anyhow!("event missing proposal"))?;
+                let deposit_amount = Amount::try_from(
+                    submit
+                        .deposit_amount
+                        .ok_or(anyhow!("event missing deposit_amount"))?,
+                )?;
+                Ok(Self::ProposalSubmit {
+                    proposal,
+                    deposit_amount,
+                    start_height: pe.start_height,
+                    end_height: pe.end_height,
+                })
+            }
+            // Validator Vote
+            x if x == Event::NAMES[1] => {
+                let pe = pb::EventValidatorVote::from_event(event.as_ref())?;
+                let body = pe
+                    .vote
+                    .and_then(|x| x.body)
+                    .ok_or(anyhow!("event missing vote body"))?;
+                let identity_key = IdentityKey::try_from(
+                    body.identity_key
+                        .ok_or(anyhow!("event missing identity_key"))?,
+                )?;
+                Ok(Self::ValidatorVote {
+                    proposal_id: body.proposal,
+                    identity_key,
+                    vote: vote_name(body.vote),
+                    voting_power: pe.voting_power,
+                })
+            }
+            // Delegator Vote
+            x if x == Event::NAMES[2] => {
+                let pe = pb::EventDelegatorVote::from_event(event.as_ref())?;
+                let body = pe
+                    .vote
+                    .and_then(|x| x.body)
+                    .ok_or(anyhow!("event missing vote body"))?;
+                let identity_key = IdentityKey::try_from(
+                    pe.validator_identity_key
+                        .ok_or(anyhow!("event missing validator_identity_key"))?,
+                )?;
+                let unbonded_amount = Amount::try_from(
+                    body.unbonded_amount
+                        .ok_or(anyhow!("event missing unbonded_amount"))?,
+                )?;
+                Ok(Self::DelegatorVote {
+                    proposal_id: body.proposal,
+                    identity_key,
+                    vote: vote_name(body.vote),
+                    unbonded_amount,
+                })
+            }
+            // Proposal Withdraw
+            x if x == Event::NAMES[3] => {
+                let pe = pb::EventProposalWithdraw::from_event(event.as_ref())?;
+                let withdraw = pe.withdraw.ok_or(anyhow!("event missing withdraw"))?;
+                Ok(Self::ProposalWithdraw {
+                    proposal_id: withdraw.proposal,
+                    reason: withdraw.reason,
+                })
+            }
+            // Proposal Passed
+            x if x == Event::NAMES[4] => {
+                let pe = pb::EventProposalPassed::from_event(event.as_ref())?;
+                let proposal = pe.proposal.ok_or(anyhow!("event missing proposal"))?;
+                Ok(Self::ProposalPassed {
+                    proposal_id: proposal.id,
+                })
+            }
+            // Proposal Failed
+            x if x == Event::NAMES[5] => {
+                let pe = pb::EventProposalFailed::from_event(event.as_ref())?;
+                let proposal = pe.proposal.ok_or(anyhow!("event missing proposal"))?;
+                Ok(Self::ProposalFailed {
+                    proposal_id: proposal.id,
+                })
+            }
+            // Proposal Slashed
+            x if x == Event::NAMES[6] => {
+                let pe = pb::EventProposalSlashed::from_event(event.as_ref())?;
+                let proposal = pe.proposal.ok_or(anyhow!("event missing proposal"))?;
+                Ok(Self::ProposalSlashed {
+                    proposal_id: proposal.id,
+                })
+            }
+            // Proposal Deposit Claim
+            x if x == Event::NAMES[7] => {
+                let pe = pb::EventProposalDepositClaim::from_event(event.as_ref())?;
+                let deposit_claim = pe
+                    .deposit_claim
+                    .ok_or(anyhow!("event missing deposit_claim"))?;
+                Ok(Self::ProposalDepositClaim {
+                    proposal_id: deposit_claim.proposal,
+                })
+            }
+            x => Err(anyhow!(format!("unrecognized event kind: {x}"))),
+        }
+    }
+}
+
+/// A component for indexing and retrieving governance proposals.
+#[derive(Debug)]
+pub struct Component {
+    event_strings: HashSet<&'static str>,
+}
+
+impl Component {
+    pub const TEMPLATES: [(&'static str, &'static str); 2] = [
+        ("proposals", include_str!("../../templates/proposals.html")),
+        ("proposal", include_str!("../../templates/proposal.html")),
+    ];
+
+    pub fn new() -> Self {
+        let event_strings = Event::NAMES.into_iter().collect();
+        Self { event_strings }
+    }
+
+    pub fn attach_to_indexer(self, indexer: pindexer::Indexer) -> pindexer::Indexer {
+        indexer.with_index(self)
+    }
+
+    /// Fetch a list of proposals, in a given range of proposal ids.
+    ///
+    /// This will be sorted in reverse order, with at most [MAX_PROPOSALS] being returned.
+    pub async fn proposals(
+        pool: &PgPool,
+        pagination: &Pagination<i64>,
+    ) -> anyhow::Result<Vec<Proposal>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    id, title, description, kind, payload, deposit_amount, start_height, end_height,
+    submitted_at, state, withdraw_reason, outcome_height, deposit_claimed, yes, no, abstain
+FROM governance_proposal_summary
+WHERE id BETWEEN $1 AND $2
+ORDER BY id DESC
+LIMIT $3;"#,
+        )
+        .bind(pagination.start)
+        .bind(pagination.stop)
+        .bind(i64::try_from(pagination.limit(MAX_PROPOSALS))?)
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch a specific proposal, by its id.
+    pub async fn proposal(pool: &PgPool, id: i64) -> anyhow::Result<Proposal> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    id, title, description, kind, payload, deposit_amount, start_height, end_height,
+    submitted_at, state, withdraw_reason, outcome_height, deposit_claimed, yes, no, abstain
+FROM governance_proposal_summary
+WHERE id = $1;"#,
+        )
+        .bind(id)
+        .fetch_one(pool)
+        .await?)
+    }
+
+    /// Fetch the votes of validators on a proposal, by descending voting power.
+    pub async fn validator_votes(pool: &PgPool, id: i64) -> anyhow::Result<Vec<ValidatorVote>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT governance_validator_vote.ik, name, vote, governance_validator_vote.voting_power, height
+FROM governance_validator_vote
+LEFT JOIN stake_validator_set ON stake_validator_set.ik = governance_validator_vote.ik
+WHERE proposal_id = $1
+ORDER BY governance_validator_vote.voting_power DESC;"#,
+        )
+        .bind(id)
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch the votes of delegators on a proposal, grouped by validator and option.
+    pub async fn delegator_votes(pool: &PgPool, id: i64) -> anyhow::Result<Vec<DelegatorVotes>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT governance_delegator_vote.ik, name, vote, COUNT(*), SUM(unbonded_amount) AS amount
+FROM governance_delegator_vote
+LEFT JOIN stake_validator_set ON stake_validator_set.ik = governance_delegator_vote.ik
+WHERE proposal_id = $1
+GROUP BY governance_delegator_vote.ik, name, vote
+ORDER BY amount DESC;"#,
+        )
+        .bind(id)
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch the voting record of a validator, most recent proposals first.
+    ///
+    /// This includes every proposal counted by its participation rate.
+    pub async fn validator_record(
+        pool: &PgPool,
+        identity: &IdentityKey,
+    ) -> anyhow::Result<Vec<VotingRecord>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT id AS proposal_id, title, state, vote, governance_validator_vote.height
+FROM governance_eligible_validator AS eligible
+JOIN governance_proposal ON governance_proposal.id = eligible.proposal_id
+LEFT JOIN governance_validator_vote
+    ON governance_validator_vote.proposal_id = eligible.proposal_id
+    AND governance_validator_vote.ik = eligible.ik
+WHERE eligible.ik = $1
+ORDER BY id DESC;"#,
+        )
+        .bind(identity.to_bytes())
+        .fetch_all(pool)
+        .await?)
+    }
+}
+
+#[async_trait]
+impl AppView for Component {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        dbtx.as_mut()
+            .execute(include_str!("governance.sql"))
+            .await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        self.event_strings.contains(type_str)
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        let ctx = Context::fetch(src_db, event).await?;
+        Event::try_from(event)?.index(dbtx, &ctx).await
+    }
+}
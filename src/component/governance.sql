@@ -0,0 +1,142 @@
+-- This component is responsible for tracking governance proposals, and the votes on them.
+
+CREATE TABLE IF NOT EXISTS governance_proposal (
+  id BIGINT PRIMARY KEY,
+  title TEXT NOT NULL,
+  description TEXT NOT NULL,
+  -- One of "signaling", "emergency", "parameter_change", "community_pool_spend",
+  -- "upgrade_plan", "freeze_ibc_client", or "unfreeze_ibc_client".
+  kind TEXT NOT NULL,
+  -- The contents specific to this kind of proposal, as pretty-printed JSON.
+  payload TEXT NOT NULL,
+  deposit_amount NUMERIC(39, 0) NOT NULL,
+  -- The heights at which voting starts and ends.
+  start_height BIGINT NOT NULL,
+  end_height BIGINT NOT NULL,
+  submitted_at TIMESTAMPTZ NOT NULL,
+  -- One of "voting", "withdrawn", "passed", "failed", or "slashed".
+  state TEXT NOT NULL,
+  -- The reason given for withdrawing the proposal, if any.
+  withdraw_reason TEXT,
+  -- The height at which the proposal passed, failed, or was slashed.
+  outcome_height BIGINT,
+  -- Whether or not the deposit has been claimed.
+  deposit_claimed BOOLEAN NOT NULL
+);
+
+-- The vote of each validator on each proposal.
+CREATE TABLE IF NOT EXISTS governance_validator_vote (
+  proposal_id BIGINT NOT NULL,
+  ik BYTEA NOT NULL,
+  -- One of "yes", "no", or "abstain".
+  vote TEXT NOT NULL,
+  -- The voting power of the validator when voting.
+  voting_power BIGINT NOT NULL,
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  PRIMARY KEY (proposal_id, ik)
+);
+
+-- Each vote cast by a delegator, with the delegation tokens of some validator.
+CREATE TABLE IF NOT EXISTS governance_delegator_vote (
+  -- The rowid of the event in the source database, which gives us a total ordering.
+  rowid BIGINT PRIMARY KEY,
+  proposal_id BIGINT NOT NULL,
+  -- The validator whose delegation tokens were used to vote.
+  ik BYTEA NOT NULL,
+  -- One of "yes", "no", or "abstain".
+  vote TEXT NOT NULL,
+  -- The amount of the staking token the delegation tokens are worth.
+  unbonded_amount NUMERIC(39, 0) NOT NULL,
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS governance_delegator_vote_proposal_idx
+  ON governance_delegator_vote (proposal_id, ik);
+
+-- The current tally of the votes on each proposal.
+--
+-- Delegators voting override the vote of their validator for their share of its power,
+-- so the power of each validator is reduced by that of the delegators who voted.
+CREATE OR REPLACE VIEW governance_tally AS
+SELECT proposal_id, vote, SUM(power) AS power
+FROM (
+  SELECT
+    proposal_id,
+    vote,
+    GREATEST(
+      voting_power - COALESCE((
+        SELECT SUM(unbonded_amount)
+        FROM governance_delegator_vote
+        WHERE governance_delegator_vote.proposal_id = governance_validator_vote.proposal_id
+        AND governance_delegator_vote.ik = governance_validator_vote.ik
+      ), 0),
+      0
+    ) AS power
+  FROM governance_validator_vote
+  UNION ALL
+  SELECT proposal_id, vote, unbonded_amount AS power
+  FROM governance_delegator_vote
+) AS votes
+GROUP BY proposal_id, vote;
+
+-- Each proposal, along with its current tally, pivoted into one column per vote.
+CREATE OR REPLACE VIEW governance_proposal_summary AS
+SELECT
+  id, title, description, kind, payload, deposit_amount, start_height, end_height,
+  submitted_at, state, withdraw_reason, outcome_height, deposit_claimed,
+  COALESCE(tally.yes, 0) AS yes,
+  COALESCE(tally.no, 0) AS no,
+  COALESCE(tally.abstain, 0) AS abstain
+FROM governance_proposal
+LEFT JOIN (
+  SELECT
+    proposal_id,
+    SUM(power) FILTER (WHERE vote = 'yes') AS yes,
+    SUM(power) FILTER (WHERE vote = 'no') AS no,
+    SUM(power) FILTER (WHERE vote = 'abstain') AS abstain
+  FROM governance_tally
+  GROUP BY proposal_id
+) AS tally ON tally.proposal_id = governance_proposal.id;
+
+-- The validators expected to vote on each proposal which has finished voting.
+--
+-- A validator is expected to vote if it was active when voting started. Validators present
+-- at genesis are active from the start, until their first state change.
+--
+-- This reads the tables of the validator component, so that component needs to be
+-- attached to the indexer first, for those tables to exist when this view is created.
+CREATE OR REPLACE VIEW governance_eligible_validator AS
+SELECT governance_proposal.id AS proposal_id, validators.ik
+FROM governance_proposal
+CROSS JOIN (SELECT DISTINCT ik FROM validator_definition) AS validators
+WHERE governance_proposal.state NOT IN ('voting', 'withdrawn')
+AND COALESCE(
+  (
+    SELECT state
+    FROM validator_state_change
+    WHERE ik = validators.ik AND height <= governance_proposal.start_height
+    ORDER BY height DESC, rowid DESC
+    LIMIT 1
+  ),
+  (
+    SELECT 'active'
+    FROM validator_definition
+    WHERE ik = validators.ik AND height = 0
+    LIMIT 1
+  )
+) = 'active';
+
+-- How often each validator has voted on the proposals it was expected to vote on.
+CREATE OR REPLACE VIEW governance_participation AS
+SELECT
+  eligible.ik,
+  COUNT(*) AS eligible,
+  COUNT(governance_validator_vote.vote) AS voted,
+  100.0 * COUNT(governance_validator_vote.vote)::FLOAT8 / COUNT(*) AS participation
+FROM governance_eligible_validator AS eligible
+LEFT JOIN governance_validator_vote
+  ON governance_validator_vote.proposal_id = eligible.proposal_id
+  AND governance_validator_vote.ik = eligible.ik
+GROUP BY eligible.ik;
@@ -0,0 +1,803 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use axum::async_trait;
+use penumbra_asset::{asset::Id as AssetId, Value};
+use penumbra_keys::address::Address;
+use penumbra_num::Amount;
+use penumbra_proto::{event::ProtoEvent, penumbra::core::component::shielded_pool::v1 as pb};
+use pindexer::{AppView, ContextualizedEvent, PgTransaction};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sqlx::{
+    postgres::PgRow,
+    types::chrono::{DateTime, Utc},
+    Executor as _, PgPool, Postgres, Transaction,
+};
+
+use super::{asset::FungibleTokenPacketData, net_flow, Context};
+use crate::pagination::Pagination;
+use crate::sql::{SQLAmount, SQLAssetId};
+
+/// The maximum number of transfers returned in a single listing.
+const MAX_TRANSFERS: u64 = 100;
+/// The maximum number of points in the value balance history of each asset.
+const MAX_BALANCE_POINTS: i64 = 500;
+
+/// A fungible token transfer in or out of Penumbra.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transfer {
+    pub height: i64,
+    pub created_at: DateTime<Utc>,
+    /// One of "inbound", "outbound", or "refund".
+    pub kind: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub asset_id: AssetId,
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: Amount,
+    pub penumbra_address: String,
+    pub foreign_address: String,
+    pub channel_id: String,
+    pub sequence: i64,
+    /// Either "timeout" or "error", for refunds.
+    pub refund_reason: Option<String>,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for Transfer {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (
+            i64,
+            DateTime<Utc>,
+            String,
+            SQLAssetId,
+            SQLAmount,
+            String,
+            String,
+            String,
+            i64,
+            Option<String>,
+        ) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            height: x.0,
+            created_at: x.1,
+            kind: x.2,
+            asset_id: x.3.into(),
+            amount: x.4.into(),
+            penumbra_address: x.5,
+            foreign_address: x.6,
+            channel_id: x.7,
+            sequence: x.8,
+            refund_reason: x.9,
+        })
+    }
+}
+
+/// A channel, along with the connection and client it goes through.
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
+pub struct Channel {
+    pub channel_id: String,
+    pub port_id: String,
+    pub connection_id: String,
+    pub counterparty_port_id: String,
+    pub counterparty_channel_id: String,
+    /// One of "init", "try", or "open".
+    pub state: String,
+    /// This is missing if we never saw the connection being created.
+    pub client_id: Option<String>,
+    pub counterparty_client_id: Option<String>,
+    pub height: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The total amount of an asset which has moved through a channel.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelFlow {
+    pub channel_id: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub asset_id: AssetId,
+    /// The amount which came into Penumbra.
+    #[serde_as(as = "DisplayFromStr")]
+    pub inbound: Amount,
+    /// The amount which left Penumbra, not counting refunds.
+    #[serde_as(as = "DisplayFromStr")]
+    pub outbound: Amount,
+    /// Whether more came in than left.
+    pub inflow: bool,
+    /// The difference between the amounts coming in and leaving.
+    #[serde_as(as = "DisplayFromStr")]
+    pub net: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for ChannelFlow {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (String, SQLAssetId, SQLAmount, SQLAmount, SQLAmount) =
+            sqlx::FromRow::from_row(row)?;
+        let inbound = Amount::from(x.2);
+        let outbound = Amount::from(x.3)
+            .checked_sub(&Amount::from(x.4))
+            .unwrap_or_default();
+        let (inflow, net) = net_flow(inbound, outbound);
+        Ok(Self {
+            channel_id: x.0,
+            asset_id: x.1.into(),
+            inbound,
+            outbound,
+            inflow,
+            net,
+        })
+    }
+}
+
+/// The amount of an asset escrowed by a channel.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValueBalance {
+    pub channel_id: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub asset_id: AssetId,
+    /// Whether more was released than escrowed, which means we're missing some transfers.
+    pub negative: bool,
+    #[serde_as(as = "DisplayFromStr")]
+    pub balance: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for ValueBalance {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (String, SQLAssetId, SQLAmount, SQLAmount) = sqlx::FromRow::from_row(row)?;
+        let (positive, balance) = net_flow(x.2.into(), x.3.into());
+        Ok(Self {
+            channel_id: x.0,
+            asset_id: x.1.into(),
+            negative: !positive,
+            balance,
+        })
+    }
+}
+
+/// The value balance of a channel for some asset, after some transfer.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValueBalancePoint {
+    pub height: i64,
+    pub created_at: DateTime<Utc>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub asset_id: AssetId,
+    /// Whether more was released than escrowed, which means we're missing some transfers.
+    pub negative: bool,
+    #[serde_as(as = "DisplayFromStr")]
+    pub balance: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for ValueBalancePoint {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (i64, DateTime<Utc>, SQLAssetId, SQLAmount, SQLAmount) =
+            sqlx::FromRow::from_row(row)?;
+        let (positive, balance) = net_flow(x.3.into(), x.4.into());
+        Ok(Self {
+            height: x.0,
+            created_at: x.1,
+            asset_id: x.2.into(),
+            negative: !positive,
+            balance,
+        })
+    }
+}
+
+/// The transfers going to and from a counterparty chain, as identified by our client for it.
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
+pub struct Counterparty {
+    pub client_id: String,
+    pub counterparty_client_id: String,
+    /// The number of channels with this counterparty which have seen transfers.
+    pub channel_count: i64,
+    pub inbound_count: i64,
+    pub outbound_count: i64,
+    pub refund_count: i64,
+    /// The number of outbound transfers neither acknowledged nor refunded yet.
+    pub pending_count: i64,
+}
+
+/// The name we use for a refund reason.
+fn refund_reason_name(reason: i32) -> &'static str {
+    match reason {
+        1 => "timeout",
+        2 => "error",
+        _ => "unspecified",
+    }
+}
+
+/// Whether a transfer moves a voucher for an asset which came in through its channel.
+///
+/// The transfer events don't include the denom, so we read it from the packet sent or received
+/// in the same transaction. A refund moves the same asset as the transfer it refunds.
+async fn is_voucher<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    src_db: &PgPool,
+    rowid: i64,
+    kind: &str,
+    channel_id: &str,
+    sequence: u64,
+) -> anyhow::Result<bool> {
+    let (packet_kind, channel_key) = match kind {
+        "inbound" => ("recv_packet", "packet_dst_channel"),
+        "outbound" => ("send_packet", "packet_src_channel"),
+        _ => {
+            let refunded: Option<(bool,)> = sqlx::query_as(
+                "SELECT voucher FROM ibc_transfer WHERE kind = 'outbound' AND channel_id = $1 AND sequence = $2;",
+            )
+            .bind(channel_id)
+            .bind(i64::try_from(sequence)?)
+            .fetch_optional(dbtx.as_mut())
+            .await?;
+            return Ok(refunded.map(|x| x.0).unwrap_or_default());
+        }
+    };
+    let packet: Option<(String, String, String)> = sqlx::query_as(
+        r#"
+SELECT data.value, src_port.value, src_channel.value
+FROM
+    events AS transfer
+    JOIN events AS packet ON packet.tx_id = transfer.tx_id AND packet.type = $2
+    JOIN attributes AS channel ON channel.event_id = packet.rowid AND channel.key = $3
+    JOIN attributes AS seq ON seq.event_id = packet.rowid AND seq.key = 'packet_sequence'
+    JOIN attributes AS data ON data.event_id = packet.rowid AND data.key = 'packet_data'
+    JOIN attributes AS src_port ON src_port.event_id = packet.rowid AND src_port.key = 'packet_src_port'
+    JOIN attributes AS src_channel ON src_channel.event_id = packet.rowid AND src_channel.key = 'packet_src_channel'
+WHERE
+    transfer.rowid = $1 AND channel.value = $4 AND seq.value = $5
+LIMIT 1;"#,
+    )
+    .bind(rowid)
+    .bind(packet_kind)
+    .bind(channel_key)
+    .bind(channel_id)
+    .bind(sequence.to_string())
+    .fetch_optional(src_db)
+    .await?;
+    // Not knowing the denom shouldn't prevent us from indexing the transfer itself.
+    let (data, src_port, src_channel) = match packet {
+        Some(x) => x,
+        None => {
+            tracing::warn!(
+                channel_id,
+                sequence,
+                "failed to find the packet of a transfer"
+            );
+            return Ok(false);
+        }
+    };
+    let packet: FungibleTokenPacketData = serde_json::from_str(&data)?;
+    // The denom of a packet is traced through the sending side of the channel: an asset going
+    // back out the way it came in, or coming back in the way it went out, is prefixed with it.
+    let returning = packet
+        .denom
+        .starts_with(&format!("{src_port}/{src_channel}/"));
+    Ok(if kind == "outbound" {
+        returning
+    } else {
+        !returning
+    })
+}
+
+/// The state a connection or channel is in after a step of its handshake.
+fn handshake_state(kind: &str) -> &'static str {
+    if kind.ends_with("_init") {
+        "init"
+    } else if kind.ends_with("_try") {
+        "try"
+    } else {
+        "open"
+    }
+}
+
+/// One of the possible events that we care about.
+#[derive(Clone, Debug)]
+enum Event {
+    /// A parsed version of [pb::EventInboundFungibleTokenTransfer],
+    /// [pb::EventOutboundFungibleTokenTransfer], or [pb::EventOutboundFungibleTokenRefund].
+    Transfer {
+        kind: &'static str,
+        value: Value,
+        penumbra_address: Address,
+        foreign_address: String,
+        channel_id: String,
+        sequence: u64,
+        refund_reason: Option<&'static str>,
+    },
+    /// A "create_client" event.
+    CreateClient {
+        client_id: String,
+        client_type: String,
+    },
+    /// One of the "connection_open_*" events.
+    ConnectionOpen {
+        state: &'static str,
+        connection_id: String,
+        client_id: String,
+        counterparty_client_id: String,
+        counterparty_connection_id: String,
+    },
+    /// An "acknowledge_packet" event.
+    Acknowledge {
+        port_id: String,
+        channel_id: String,
+        sequence: u64,
+    },
+    /// One of the "channel_open_*" events.
+    ChannelOpen {
+        state: &'static str,
+        channel_id: String,
+        port_id: String,
+        connection_id: String,
+        counterparty_port_id: String,
+        counterparty_channel_id: String,
+    },
+}
+
+impl Event {
+    const NAMES: [&'static str; 13] = [
+        "penumbra.core.component.shielded_pool.v1.EventInboundFungibleTokenTransfer",
+        "penumbra.core.component.shielded_pool.v1.EventOutboundFungibleTokenTransfer",
+        "penumbra.core.component.shielded_pool.v1.EventOutboundFungibleTokenRefund",
+        "create_client",
+        "connection_open_init",
+        "connection_open_try",
+        "connection_open_ack",
+        "connection_open_confirm",
+        "channel_open_init",
+        "channel_open_try",
+        "channel_open_ack",
+        "channel_open_confirm",
+        "acknowledge_packet",
+    ];
+
+    /// Index this event, using the handle to the postgres transaction.
+    async fn index<'d>(
+        self,
+        dbtx: &mut Transaction<'d, Postgres>,
+        src_db: &PgPool,
+        ctx: &Context,
+    ) -> anyhow::Result<()> {
+        match self {
+            Event::Transfer {
+                kind,
+                value,
+                penumbra_address,
+                foreign_address,
+                channel_id,
+                sequence,
+                refund_reason,
+            } => {
+                let voucher =
+                    is_voucher(dbtx, src_db, ctx.rowid, kind, &channel_id, sequence).await?;
+                sqlx::query(
+                    r#"
+                INSERT INTO ibc_transfer
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12);
+                "#,
+                )
+                .bind(ctx.rowid)
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .bind(kind)
+                .bind(SQLAssetId::from(value.asset_id))
+                .bind(SQLAmount::from(value.amount))
+                .bind(penumbra_address.to_string())
+                .bind(foreign_address)
+                .bind(channel_id)
+                .bind(i64::try_from(sequence)?)
+                .bind(refund_reason)
+                .bind(voucher)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::CreateClient {
+                client_id,
+                client_type,
+            } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO ibc_client
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (client_id) DO NOTHING;
+                "#,
+                )
+                .bind(client_id)
+                .bind(client_type)
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::ConnectionOpen {
+                state,
+                connection_id,
+                client_id,
+                counterparty_client_id,
+                counterparty_connection_id,
+            } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO ibc_connection
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (connection_id) DO UPDATE SET
+                    counterparty_connection_id = EXCLUDED.counterparty_connection_id,
+                    state = EXCLUDED.state;
+                "#,
+                )
+                .bind(connection_id)
+                .bind(client_id)
+                .bind(counterparty_client_id)
+                .bind(counterparty_connection_id)
+                .bind(state)
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::Acknowledge {
+                port_id,
+                channel_id,
+                sequence,
+            } => {
+                // Only acknowledgements of transfers concern us.
+                if port_id != "transfer" {
+                    return Ok(());
+                }
+                sqlx::query(
+                    r#"
+                INSERT INTO ibc_acknowledgement
+                VALUES ($1, $2, $3)
+                ON CONFLICT (channel_id, sequence) DO NOTHING;
+                "#,
+                )
+                .bind(channel_id)
+                .bind(i64::try_from(sequence)?)
+                .bind(ctx.height)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::ChannelOpen {
+                state,
+                channel_id,
+                port_id,
+                connection_id,
+                counterparty_port_id,
+                counterparty_channel_id,
+            } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO ibc_channel
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (channel_id) DO UPDATE SET
+                    counterparty_channel_id = EXCLUDED.counterparty_channel_id,
+                    state = EXCLUDED.state;
+                "#,
+                )
+                .bind(channel_id)
+                .bind(port_id)
+                .bind(connection_id)
+                .bind(counterparty_port_id)
+                .bind(counterparty_channel_id)
+                .bind(state)
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a ContextualizedEvent> for Event {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &'a ContextualizedEvent) -> Result<Self, Self::Error> {
+        match event.event.kind.as_str() {
+            // Inbound Transfer
+            x if x == Event::NAMES[0] => {
+                let pe = pb::EventInboundFungibleTokenTransfer::from_event(event.as_ref())?;
+                let meta = pe.meta.ok_or(anyhow!("event missing meta"))?;
+                Ok(Self::Transfer {
+                    kind: "inbound",
+                    value: Value::try_from(pe.value.ok_or(anyhow!("event missing value"))?)?,
+                    penumbra_address: Address::try_from(
+                        pe.receiver.ok_or(anyhow!("event missing receiver"))?,
+                    )?,
+                    foreign_address: pe.sender,
+                    channel_id: meta.channel,
+                    sequence: meta.sequence,
+                    refund_reason: None,
+                })
+            }
+            // Outbound Transfer
+            x if x == Event::NAMES[1] => {
+                let pe = pb::EventOutboundFungibleTokenTransfer::from_event(event.as_ref())?;
+                let meta = pe.meta.ok_or(anyhow!("event missing meta"))?;
+                Ok(Self::Transfer {
+                    kind: "outbound",
+                    value: Value::try_from(pe.value.ok_or(anyhow!("event missing value"))?)?,
+                    penumbra_address: Address::try_from(
+                        pe.sender.ok_or(anyhow!("event missing sender"))?,
+                    )?,
+                    foreign_address: pe.receiver,
+                    channel_id: meta.channel,
+                    sequence: meta.sequence,
+                    refund_reason: None,
+                })
+            }
+            // Outbound Refund
+            x if x == Event::NAMES[2] => {
+                let pe = pb::EventOutboundFungibleTokenRefund::from_event(event.as_ref())?;
+                let meta = pe.meta.ok_or(anyhow!("event missing meta"))?;
+                Ok(Self::Transfer {
+                    kind: "refund",
+                    value: Value::try_from(pe.value.ok_or(anyhow!("event missing value"))?)?,
+                    penumbra_address: Address::try_from(
+                        pe.sender.ok_or(anyhow!("event missing sender"))?,
+                    )?,
+                    foreign_address: pe.receiver,
+                    channel_id: meta.channel,
+                    sequence: meta.sequence,
+                    refund_reason: Some(refund_reason_name(pe.reason)),
+                })
+            }
+            // Create Client
+            x if x == Event::NAMES[3] => Ok(Self::CreateClient {
+                client_id: super::attribute(event, "client_id")?.to_string(),
+                client_type: super::attribute(event, "client_type")?.to_string(),
+            }),
+            // Connection Open
+            x if Event::NAMES[4..8].contains(&x) => Ok(Self::ConnectionOpen {
+                state: handshake_state(x),
+                connection_id: super::attribute(event, "connection_id")?.to_string(),
+                client_id: super::attribute(event, "client_id")?.to_string(),
+                counterparty_client_id: super::attribute(event, "counterparty_client_id")?
+                    .to_string(),
+                counterparty_connection_id: super::attribute(event, "counterparty_connection_id")?
+                    .to_string(),
+            }),
+            // Channel Open
+            x if Event::NAMES[8..12].contains(&x) => Ok(Self::ChannelOpen {
+                state: handshake_state(x),
+                channel_id: super::attribute(event, "channel_id")?.to_string(),
+                port_id: super::attribute(event, "port_id")?.to_string(),
+                connection_id: super::attribute(event, "connection_id")?.to_string(),
+                counterparty_port_id: super::attribute(event, "counterparty_port_id")?.to_string(),
+                counterparty_channel_id: super::attribute(event, "counterparty_channel_id")?
+                    .to_string(),
+            }),
+            // Acknowledge Packet
+            x if x == Event::NAMES[12] => Ok(Self::Acknowledge {
+                port_id: super::attribute(event, "packet_src_port")?.to_string(),
+                channel_id: super::attribute(event, "packet_src_channel")?.to_string(),
+                sequence: super::attribute(event, "packet_sequence")?.parse()?,
+            }),
+            x => Err(anyhow!(format!("unrecognized event kind: {x}"))),
+        }
+    }
+}
+
+/// A component for indexing and retrieving IBC transfers and channels.
+#[derive(Debug)]
+pub struct Component {
+    event_strings: HashSet<&'static str>,
+}
+
+impl Component {
+    pub const TEMPLATES: [(&'static str, &'static str); 3] = [
+        (
+            "ibc_transfers",
+            include_str!("../../templates/ibc_transfers.html"),
+        ),
+        (
+            "ibc_channels",
+            include_str!("../../templates/ibc_channels.html"),
+        ),
+        (
+            "ibc_channel",
+            include_str!("../../templates/ibc_channel.html"),
+        ),
+    ];
+
+    pub fn new() -> Self {
+        let event_strings = Event::NAMES.into_iter().collect();
+        Self { event_strings }
+    }
+
+    pub fn attach_to_indexer(self, indexer: pindexer::Indexer) -> pindexer::Indexer {
+        indexer.with_index(self)
+    }
+
+    /// Fetch a list of transfers, in a given range of heights.
+    ///
+    /// This will be sorted in reverse order, with at most [MAX_TRANSFERS] being returned.
+    pub async fn transfers(
+        pool: &PgPool,
+        pagination: &Pagination<i64>,
+    ) -> anyhow::Result<Vec<Transfer>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    height, created_at, kind, asset_id, amount, penumbra_address, foreign_address,
+    channel_id, sequence, refund_reason
+FROM ibc_transfer
+WHERE height BETWEEN $1 AND $2
+ORDER BY rowid DESC
+LIMIT $3;"#,
+        )
+        .bind(pagination.start)
+        .bind(pagination.stop)
+        .bind(i64::try_from(pagination.limit(MAX_TRANSFERS))?)
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch every channel, in the order they were created.
+    pub async fn channels(pool: &PgPool) -> anyhow::Result<Vec<Channel>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    channel_id, port_id, ibc_channel.connection_id, counterparty_port_id,
+    counterparty_channel_id, ibc_channel.state, client_id, counterparty_client_id,
+    ibc_channel.height, ibc_channel.created_at
+FROM ibc_channel
+LEFT JOIN ibc_connection ON ibc_connection.connection_id = ibc_channel.connection_id
+ORDER BY ibc_channel.height;"#,
+        )
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch a specific channel, by its id.
+    pub async fn channel(pool: &PgPool, channel_id: &str) -> anyhow::Result<Channel> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    channel_id, port_id, ibc_channel.connection_id, counterparty_port_id,
+    counterparty_channel_id, ibc_channel.state, client_id, counterparty_client_id,
+    ibc_channel.height, ibc_channel.created_at
+FROM ibc_channel
+LEFT JOIN ibc_connection ON ibc_connection.connection_id = ibc_channel.connection_id
+WHERE channel_id = $1;"#,
+        )
+        .bind(channel_id)
+        .fetch_one(pool)
+        .await?)
+    }
+
+    /// Fetch the current value balance of each asset, for one channel, or all of them.
+    pub async fn value_balances(
+        pool: &PgPool,
+        channel_id: Option<&str>,
+    ) -> anyhow::Result<Vec<ValueBalance>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT channel_id, asset_id, SUM(escrowed), SUM(released)
+FROM ibc_value_balance_change
+WHERE $1::TEXT IS NULL OR channel_id = $1
+GROUP BY channel_id, asset_id
+ORDER BY channel_id, asset_id;"#,
+        )
+        .bind(channel_id)
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch the value balance of each asset of a channel after each transfer, in order.
+    ///
+    /// Only the last [MAX_BALANCE_POINTS] points of each asset are returned.
+    pub async fn value_balance_history(
+        pool: &PgPool,
+        channel_id: &str,
+    ) -> anyhow::Result<Vec<ValueBalancePoint>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT height, created_at, asset_id, escrowed, released
+FROM (
+    SELECT
+        rowid,
+        height,
+        created_at,
+        asset_id,
+        SUM(escrowed) OVER (PARTITION BY asset_id ORDER BY rowid) AS escrowed,
+        SUM(released) OVER (PARTITION BY asset_id ORDER BY rowid) AS released,
+        ROW_NUMBER() OVER (PARTITION BY asset_id ORDER BY rowid DESC) AS n
+    FROM ibc_value_balance_change
+    WHERE channel_id = $1
+) AS points
+WHERE n <= $2
+ORDER BY asset_id, rowid;"#,
+        )
+        .bind(channel_id)
+        .bind(MAX_BALANCE_POINTS)
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch the number of transfers going to and from each counterparty chain.
+    pub async fn counterparties(pool: &PgPool) -> anyhow::Result<Vec<Counterparty>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    client_id,
+    counterparty_client_id,
+    COUNT(DISTINCT ibc_transfer.channel_id) AS channel_count,
+    COUNT(*) FILTER (WHERE kind = 'inbound') AS inbound_count,
+    COUNT(*) FILTER (WHERE kind = 'outbound') AS outbound_count,
+    COUNT(*) FILTER (WHERE kind = 'refund') AS refund_count,
+    COUNT(*) FILTER (WHERE kind = 'outbound'
+        AND NOT EXISTS (
+            SELECT 1 FROM ibc_acknowledgement
+            WHERE ibc_acknowledgement.channel_id = ibc_transfer.channel_id
+            AND ibc_acknowledgement.sequence = ibc_transfer.sequence
+        )
+        AND NOT EXISTS (
+            SELECT 1 FROM ibc_transfer AS refund
+            WHERE refund.kind = 'refund'
+            AND refund.channel_id = ibc_transfer.channel_id
+            AND refund.sequence = ibc_transfer.sequence
+        )
+    ) AS pending_count
+FROM ibc_transfer
+JOIN ibc_channel ON ibc_channel.channel_id = ibc_transfer.channel_id
+JOIN ibc_connection ON ibc_connection.connection_id = ibc_channel.connection_id
+GROUP BY client_id, counterparty_client_id
+ORDER BY client_id;"#,
+        )
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch the total amount of each asset which has moved through each channel.
+    pub async fn channel_flows(pool: &PgPool) -> anyhow::Result<Vec<ChannelFlow>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    channel_id,
+    asset_id,
+    COALESCE(SUM(amount) FILTER (WHERE kind = 'inbound'), 0),
+    COALESCE(SUM(amount) FILTER (WHERE kind = 'outbound'), 0),
+    COALESCE(SUM(amount) FILTER (WHERE kind = 'refund'), 0)
+FROM ibc_transfer
+GROUP BY channel_id, asset_id
+ORDER BY channel_id, asset_id;"#,
+        )
+        .fetch_all(pool)
+        .await?)
+    }
+}
+
+#[async_trait]
+impl AppView for Component {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        dbtx.as_mut().execute(include_str!("ibc.sql")).await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        self.event_strings.contains(type_str)
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        let ctx = Context::fetch(src_db, event).await?;
+        Event::try_from(event)?.index(dbtx, src_db, &ctx).await
+    }
+}
@@ -0,0 +1,93 @@
+-- This component is responsible for tracking IBC transfers, and the clients, connections,
+-- and channels they go through.
+
+-- Each fungible token transfer in or out of Penumbra.
+CREATE TABLE IF NOT EXISTS ibc_transfer (
+  -- The rowid of the event in the source database, which gives us a total ordering.
+  rowid BIGINT PRIMARY KEY,
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  -- One of:
+  -- - "inbound", funds coming into Penumbra,
+  -- - "outbound", funds leaving Penumbra, through an Ics20Withdrawal,
+  -- - "refund", outbound funds coming back, because the transfer timed out or failed.
+  kind TEXT NOT NULL,
+  asset_id BYTEA NOT NULL,
+  amount NUMERIC(39, 0) NOT NULL,
+  -- The address on the Penumbra side of the transfer.
+  penumbra_address TEXT NOT NULL,
+  -- The address on the other side of the transfer.
+  foreign_address TEXT NOT NULL,
+  -- The channel on the Penumbra side.
+  channel_id TEXT NOT NULL,
+  sequence BIGINT NOT NULL,
+  -- Either "timeout" or "error", for refunds.
+  refund_reason TEXT,
+  -- Whether the asset came in through this channel, which means that it's minted when received,
+  -- and burned when sent back, rather than being escrowed.
+  voucher BOOLEAN NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS ibc_transfer_height_idx ON ibc_transfer (height);
+CREATE INDEX IF NOT EXISTS ibc_transfer_channel_idx ON ibc_transfer (channel_id, asset_id);
+
+CREATE TABLE IF NOT EXISTS ibc_client (
+  client_id TEXT PRIMARY KEY,
+  client_type TEXT NOT NULL,
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS ibc_connection (
+  connection_id TEXT PRIMARY KEY,
+  client_id TEXT NOT NULL,
+  counterparty_client_id TEXT NOT NULL,
+  -- This is empty until the counterparty has created its end.
+  counterparty_connection_id TEXT NOT NULL,
+  -- One of "init", "try", or "open".
+  state TEXT NOT NULL,
+  -- The height at which we first saw this connection.
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS ibc_channel (
+  channel_id TEXT PRIMARY KEY,
+  port_id TEXT NOT NULL,
+  connection_id TEXT NOT NULL,
+  counterparty_port_id TEXT NOT NULL,
+  -- This is empty until the counterparty has created its end.
+  counterparty_channel_id TEXT NOT NULL,
+  -- One of "init", "try", or "open".
+  state TEXT NOT NULL,
+  -- The height at which we first saw this channel.
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+-- Each acknowledgement of a transfer sent from Penumbra.
+--
+-- Together with refunds, this tells us which outbound transfers are still pending.
+CREATE TABLE IF NOT EXISTS ibc_acknowledgement (
+  channel_id TEXT NOT NULL,
+  sequence BIGINT NOT NULL,
+  height BIGINT NOT NULL,
+  PRIMARY KEY (channel_id, sequence)
+);
+
+-- How each transfer changes the value balance of its channel.
+--
+-- Penumbra escrows the assets it sends out, and releases them when they come back, or are
+-- refunded. Assets which came in through a channel are minted and burned instead, so
+-- they don't count towards its balance.
+CREATE OR REPLACE VIEW ibc_value_balance_change AS
+SELECT
+  rowid,
+  height,
+  created_at,
+  channel_id,
+  asset_id,
+  CASE WHEN kind = 'outbound' THEN amount ELSE 0 END AS escrowed,
+  CASE WHEN kind = 'outbound' THEN 0 ELSE amount END AS released
+FROM ibc_transfer
+WHERE NOT voucher;
@@ -1,3 +1,188 @@
+pub mod action;
+pub mod asset;
 pub mod block;
+pub mod community_pool;
 pub mod dex;
+pub mod epoch;
+pub mod governance;
+pub mod ibc;
+pub mod shielded_pool;
+pub mod slashing;
+pub mod supply;
+pub mod transaction;
 pub mod validator;
+
+use anyhow::anyhow;
+use penumbra_asset::{asset::Id as AssetId, STAKING_TOKEN_ASSET_ID};
+use penumbra_num::Amount;
+use penumbra_proto::{
+    penumbra::core::{
+        app::v1::{genesis_app_state, GenesisAppState, GenesisContent},
+        transaction::v1 as pb,
+    },
+    Message as _,
+};
+use pindexer::{ContextualizedEvent, PgPool};
+use sqlx::types::chrono::{DateTime, Utc};
+use tendermint_proto::v0_37::abci::TxResult;
+
+use action::Action;
+
+/// Fetch the time of the block containing a given event, using its rowid in the source database.
+pub async fn event_time(src_db: &PgPool, rowid: i64) -> anyhow::Result<DateTime<Utc>> {
+    let (created_at,): (DateTime<Utc>,) = sqlx::query_as(
+        "SELECT blocks.created_at FROM events JOIN blocks ON events.block_id = blocks.rowid WHERE events.rowid = $1;",
+    )
+    .bind(rowid)
+    .fetch_one(src_db)
+    .await?;
+    Ok(created_at)
+}
+
+/// Parse the content of the genesis state, if we're starting from genesis.
+///
+/// A chain started from a checkpoint doesn't have any content for us to learn from.
+pub fn genesis_content(app_state: &serde_json::Value) -> anyhow::Result<Option<GenesisContent>> {
+    let app_state: GenesisAppState = serde_json::from_value(app_state.clone())?;
+    match app_state.genesis_app_state {
+        Some(genesis_app_state::GenesisAppState::GenesisContent(x)) => Ok(Some(x)),
+        _ => Ok(None),
+    }
+}
+
+/// Information about where an event happened.
+#[derive(Clone, Copy, Debug)]
+pub struct Context {
+    /// The rowid of the event in the source database.
+    pub rowid: i64,
+    pub height: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Context {
+    pub async fn fetch(src_db: &PgPool, event: &ContextualizedEvent) -> anyhow::Result<Self> {
+        Ok(Self {
+            rowid: event.local_rowid,
+            height: i64::try_from(event.block_height)?,
+            created_at: event_time(src_db, event.local_rowid).await?,
+        })
+    }
+}
+
+/// A transaction included in a block, decoded from the source database.
+#[derive(Clone, Debug)]
+pub struct IndexedTransaction {
+    /// The hash of the transaction, in lowercase hex.
+    pub hash: String,
+    pub height: i64,
+    /// The position of the transaction within its block.
+    pub index: i32,
+    pub created_at: DateTime<Utc>,
+    /// The result code of the transaction, which is 0 if it succeeded.
+    pub code: u32,
+    pub fee_amount: Amount,
+    pub fee_asset_id: AssetId,
+    pub actions: Vec<Action>,
+}
+
+impl IndexedTransaction {
+    /// Fetch and decode the transaction behind a "tx" event.
+    ///
+    /// The cometbft sink emits one "tx" event for the hash, and one for the height,
+    /// so this returns `None` for the latter, to avoid indexing each transaction twice.
+    pub async fn fetch(
+        src_db: &PgPool,
+        event: &ContextualizedEvent,
+    ) -> anyhow::Result<Option<Self>> {
+        if !event.event.attributes.iter().any(|x| x.key == "hash") {
+            return Ok(None);
+        }
+
+        let (hash, height, index, created_at, tx_result): (
+            String,
+            i64,
+            i32,
+            DateTime<Utc>,
+            Vec<u8>,
+        ) = sqlx::query_as(
+            r#"
+SELECT
+    lower(tx_results.tx_hash),
+    blocks.height,
+    tx_results.index,
+    blocks.created_at,
+    tx_results.tx_result
+FROM
+    events
+    JOIN tx_results ON events.tx_id = tx_results.rowid
+    JOIN blocks ON tx_results.block_id = blocks.rowid
+WHERE
+    events.rowid = $1;"#,
+        )
+        .bind(event.local_rowid)
+        .fetch_one(src_db)
+        .await?;
+
+        let tx_result = TxResult::decode(&tx_result[..])?;
+        let code = tx_result.result.map(|x| x.code).unwrap_or_default();
+        let body = pb::Transaction::decode(&tx_result.tx[..])?
+            .body
+            .ok_or(anyhow!("transaction missing body"))?;
+        let (fee_amount, fee_asset_id) = match body.transaction_parameters.and_then(|x| x.fee) {
+            None => (Amount::zero(), *STAKING_TOKEN_ASSET_ID),
+            Some(fee) => (
+                fee.amount
+                    .map(Amount::try_from)
+                    .transpose()?
+                    .unwrap_or(Amount::zero()),
+                fee.asset_id
+                    .map(AssetId::try_from)
+                    .transpose()?
+                    .unwrap_or(*STAKING_TOKEN_ASSET_ID),
+            ),
+        };
+        let actions = body
+            .actions
+            .into_iter()
+            .map(|x| Action::decode(x.action))
+            .collect();
+
+        Ok(Some(Self {
+            hash,
+            height,
+            index,
+            created_at,
+            code,
+            fee_amount,
+            fee_asset_id,
+            actions,
+        }))
+    }
+
+    /// Whether the transaction succeeded.
+    ///
+    /// Failed transactions still appear in blocks, but none of their effects were applied,
+    /// so components should only index the actions of successful ones.
+    pub fn succeeded(&self) -> bool {
+        self.code == 0
+    }
+}
+
+/// Get the value of an attribute of an event, by its key.
+pub fn attribute<'a>(event: &'a ContextualizedEvent, key: &str) -> anyhow::Result<&'a str> {
+    event
+        .event
+        .attributes
+        .iter()
+        .find(|x| x.key == key)
+        .map(|x| x.value.as_str())
+        .ok_or(anyhow!("event missing attribute {}", key))
+}
+
+/// Whether more flowed in than out, and by how much they differ.
+pub fn net_flow(inflow: Amount, outflow: Amount) -> (bool, Amount) {
+    match inflow.checked_sub(&outflow) {
+        Some(net) => (true, net),
+        None => (false, outflow.checked_sub(&inflow).unwrap_or_default()),
+    }
+}
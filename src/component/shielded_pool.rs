@@ -0,0 +1,205 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use axum::async_trait;
+use pindexer::{AppView, ContextualizedEvent, PgTransaction};
+use serde::{Deserialize, Serialize};
+use sqlx::{
+    types::chrono::{DateTime, Utc},
+    Executor as _, PgPool, Postgres, Transaction,
+};
+
+use super::Context;
+
+/// The maximum number of days of activity returned.
+const MAX_DAYS: i64 = 365;
+
+/// The number of notes created and spent in a single block, and up to that block.
+#[derive(Debug, Clone, Default, sqlx::FromRow, Serialize, Deserialize)]
+pub struct BlockActivity {
+    pub outputs: i64,
+    pub spends: i64,
+    pub total_outputs: i64,
+    pub total_spends: i64,
+}
+
+/// The number of notes created and spent over a single day, and up to the end of that day.
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
+pub struct DailyActivity {
+    pub day: DateTime<Utc>,
+    pub outputs: i64,
+    pub spends: i64,
+    pub total_outputs: i64,
+    pub total_spends: i64,
+}
+
+/// One of the possible events that we care about.
+#[derive(Clone, Debug)]
+enum Event {
+    /// An EventOutput, revealing a new note commitment.
+    Output,
+    /// An EventSpend, revealing a nullifier.
+    Spend,
+}
+
+impl Event {
+    const NAMES: [&'static str; 2] = [
+        "penumbra.core.component.shielded_pool.v1.EventOutput",
+        "penumbra.core.component.shielded_pool.v1.EventSpend",
+    ];
+
+    /// Index this event, using the handle to the postgres transaction.
+    async fn index<'d>(
+        self,
+        dbtx: &mut Transaction<'d, Postgres>,
+        ctx: &Context,
+    ) -> anyhow::Result<()> {
+        let (outputs, spends): (i64, i64) = match self {
+            Event::Output => (1, 0),
+            Event::Spend => (0, 1),
+        };
+        // Blocks are indexed in order, so the totals of the previous row are already final.
+        sqlx::query(
+            r#"
+        INSERT INTO shielded_pool_block
+        VALUES (
+            $1,
+            $2,
+            $3,
+            $4,
+            COALESCE((
+                SELECT total_outputs FROM shielded_pool_block
+                WHERE height < $1 ORDER BY height DESC LIMIT 1
+            ), 0) + $3,
+            COALESCE((
+                SELECT total_spends FROM shielded_pool_block
+                WHERE height < $1 ORDER BY height DESC LIMIT 1
+            ), 0) + $4
+        )
+        ON CONFLICT (height) DO UPDATE SET
+            output_count = shielded_pool_block.output_count + EXCLUDED.output_count,
+            spend_count = shielded_pool_block.spend_count + EXCLUDED.spend_count,
+            total_outputs = shielded_pool_block.total_outputs + EXCLUDED.output_count,
+            total_spends = shielded_pool_block.total_spends + EXCLUDED.spend_count;
+        "#,
+        )
+        .bind(ctx.height)
+        .bind(ctx.created_at)
+        .bind(outputs)
+        .bind(spends)
+        .execute(dbtx.as_mut())
+        .await?;
+        Ok(())
+    }
+}
+
+impl<'a> TryFrom<&'a ContextualizedEvent> for Event {
+    type Error = anyhow::Error;
+
+    // We only need to count these events, so there's no point in parsing their contents.
+    fn try_from(event: &'a ContextualizedEvent) -> Result<Self, Self::Error> {
+        match event.event.kind.as_str() {
+            x if x == Event::NAMES[0] => Ok(Self::Output),
+            x if x == Event::NAMES[1] => Ok(Self::Spend),
+            x => Err(anyhow!(format!("unrecognized event kind: {x}"))),
+        }
+    }
+}
+
+/// A component for counting the activity of the shielded pool.
+#[derive(Debug)]
+pub struct Component {
+    event_strings: HashSet<&'static str>,
+}
+
+impl Component {
+    pub const TEMPLATES: [(&'static str, &'static str); 1] = [(
+        "shielded_pool",
+        include_str!("../../templates/shielded_pool.html"),
+    )];
+
+    pub fn new() -> Self {
+        let event_strings = Event::NAMES.into_iter().collect();
+        Self { event_strings }
+    }
+
+    pub fn attach_to_indexer(self, indexer: pindexer::Indexer) -> pindexer::Indexer {
+        indexer.with_index(self)
+    }
+
+    /// Fetch the activity of a specific block, by height.
+    pub async fn block_activity(pool: &PgPool, height: i64) -> anyhow::Result<BlockActivity> {
+        // The latest row at or before this height has the totals, even without any activity here.
+        let activity: Option<BlockActivity> = sqlx::query_as(
+            r#"
+SELECT
+    CASE WHEN height = $1 THEN output_count ELSE 0 END AS outputs,
+    CASE WHEN height = $1 THEN spend_count ELSE 0 END AS spends,
+    total_outputs,
+    total_spends
+FROM shielded_pool_block
+WHERE height <= $1
+ORDER BY height DESC
+LIMIT 1;"#,
+        )
+        .bind(height)
+        .fetch_optional(pool)
+        .await?;
+        Ok(activity.unwrap_or_default())
+    }
+
+    /// Fetch the activity of each day, in order.
+    ///
+    /// Only the last [MAX_DAYS] days with any activity are returned.
+    pub async fn daily_activity(pool: &PgPool) -> anyhow::Result<Vec<DailyActivity>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT day, outputs, spends, total_outputs, total_spends
+FROM (
+    SELECT
+        date_trunc('day', created_at) AS day,
+        SUM(output_count)::BIGINT AS outputs,
+        SUM(spend_count)::BIGINT AS spends,
+        MAX(total_outputs) AS total_outputs,
+        MAX(total_spends) AS total_spends
+    FROM shielded_pool_block
+    GROUP BY day
+    ORDER BY day DESC
+    LIMIT $1
+) AS recent
+ORDER BY day;"#,
+        )
+        .bind(MAX_DAYS)
+        .fetch_all(pool)
+        .await?)
+    }
+}
+
+#[async_trait]
+impl AppView for Component {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        dbtx.as_mut()
+            .execute(include_str!("shielded_pool.sql"))
+            .await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        self.event_strings.contains(type_str)
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        let ctx = Context::fetch(src_db, event).await?;
+        Event::try_from(event)?.index(dbtx, &ctx).await
+    }
+}
@@ -0,0 +1,18 @@
+-- This component is responsible for counting the notes created and spent in the shielded pool.
+--
+-- Only the number of note commitments and nullifiers is kept, nothing about their contents.
+
+CREATE TABLE IF NOT EXISTS shielded_pool_block (
+  height BIGINT PRIMARY KEY,
+  created_at TIMESTAMPTZ NOT NULL,
+  -- The number of note commitments created.
+  output_count BIGINT NOT NULL,
+  -- The number of nullifiers revealed.
+  spend_count BIGINT NOT NULL,
+  -- The number of note commitments created up to and including this block.
+  total_outputs BIGINT NOT NULL,
+  -- The number of nullifiers revealed up to and including this block.
+  total_spends BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS shielded_pool_block_created_at_idx ON shielded_pool_block (created_at);
@@ -0,0 +1,320 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use axum::async_trait;
+use decaf377_rdsa::VerificationKeyBytes;
+use penumbra_proto::{event::ProtoEvent, penumbra::core::component::stake::v1 as pb};
+use penumbra_stake::{validator, IdentityKey};
+use pindexer::{AppView, ContextualizedEvent, PgTransaction};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sqlx::{
+    postgres::PgRow,
+    types::chrono::{DateTime, Utc},
+    Executor as _, PgPool, Postgres, Transaction,
+};
+
+use super::Context;
+use crate::pagination::Pagination;
+
+/// The maximum number of slashing events returned in a single listing.
+const MAX_SLASHES: u64 = 100;
+
+/// A validator being jailed or tombstoned, along with the penalty applied.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Slash {
+    pub height: i64,
+    pub created_at: DateTime<Utc>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub identity: IdentityKey,
+    /// The name of the validator, if it still exists.
+    pub name: Option<String>,
+    /// One of "downtime" or "double_sign".
+    pub reason: String,
+    /// The height of the misbehavior, for double signing.
+    pub evidence_height: Option<i64>,
+    /// The penalty applied in this block, as a percentage, if any.
+    pub penalty_percent: Option<f64>,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for Slash {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (
+            i64,
+            DateTime<Utc>,
+            [u8; 32],
+            Option<String>,
+            String,
+            Option<i64>,
+            Option<f64>,
+        ) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            height: x.0,
+            created_at: x.1,
+            identity: IdentityKey(VerificationKeyBytes::from(x.2)),
+            name: x.3,
+            reason: x.4,
+            evidence_height: x.5,
+            penalty_percent: x.6,
+        })
+    }
+}
+
+/// One of the possible events that we care about.
+#[derive(Clone, Debug)]
+enum Event {
+    /// A parsed version of [pb::EventValidatorStateChange], when a validator is jailed.
+    Jail { identity_key: IdentityKey },
+    /// Any other [pb::EventValidatorStateChange], which we ignore.
+    OtherStateChange,
+    /// A parsed version of [pb::EventTombstoneValidator].
+    Tombstone {
+        identity_key: IdentityKey,
+        evidence_height: u64,
+        voting_power: u64,
+    },
+    /// A parsed version of [pb::EventSlashingPenaltyApplied].
+    PenaltyApplied {
+        identity_key: IdentityKey,
+        epoch_index: u64,
+        penalty: u64,
+    },
+}
+
+impl Event {
+    const NAMES: [&'static str; 3] = [
+        "penumbra.core.component.stake.v1.EventValidatorStateChange",
+        "penumbra.core.component.stake.v1.EventTombstoneValidator",
+        "penumbra.core.component.stake.v1.EventSlashingPenaltyApplied",
+    ];
+
+    /// Index this event, using the handle to the postgres transaction.
+    async fn index<'d>(
+        self,
+        dbtx: &mut Transaction<'d, Postgres>,
+        ctx: &Context,
+    ) -> anyhow::Result<()> {
+        match self {
+            Event::Jail { identity_key } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO slashing_event
+                VALUES ($1, $2, $3, $4, 'downtime', NULL, NULL);
+                "#,
+                )
+                .bind(ctx.rowid)
+                .bind(identity_key.to_bytes())
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::OtherStateChange => Ok(()),
+            Event::Tombstone {
+                identity_key,
+                evidence_height,
+                voting_power,
+            } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO slashing_event
+                VALUES ($1, $2, $3, $4, 'double_sign', $5, $6);
+                "#,
+                )
+                .bind(ctx.rowid)
+                .bind(identity_key.to_bytes())
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .bind(i64::try_from(evidence_height)?)
+                .bind(i64::try_from(voting_power)?)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::PenaltyApplied {
+                identity_key,
+                epoch_index,
+                penalty,
+            } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO slashing_penalty
+                VALUES ($1, $2, $3, $4, $5, $6);
+                "#,
+                )
+                .bind(ctx.rowid)
+                .bind(identity_key.to_bytes())
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .bind(i64::try_from(epoch_index)?)
+                .bind(i64::try_from(penalty)?)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a ContextualizedEvent> for Event {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &'a ContextualizedEvent) -> Result<Self, Self::Error> {
+        match event.event.kind.as_str() {
+            // State Change
+            x if x == Event::NAMES[0] => {
+                let pe = pb::EventValidatorStateChange::from_event(event.as_ref())?;
+                let identity_key = IdentityKey::try_from(
+                    pe.identity_key
+                        .ok_or(anyhow!("event missing identity_key"))?,
+                )?;
+                let state =
+                    validator::State::try_from(pe.state.ok_or(anyhow!("event missing state"))?)?;
+                // Validators are only ever jailed for downtime.
+                match state {
+                    validator::State::Jailed => Ok(Self::Jail { identity_key }),
+                    _ => Ok(Self::OtherStateChange),
+                }
+            }
+            // Tombstone
+            x if x == Event::NAMES[1] => {
+                let pe = pb::EventTombstoneValidator::from_event(event.as_ref())?;
+                let identity_key = IdentityKey::try_from(
+                    pe.identity_key
+                        .ok_or(anyhow!("event missing identity_key"))?,
+                )?;
+                Ok(Self::Tombstone {
+                    identity_key,
+                    evidence_height: pe.evidence_height,
+                    voting_power: pe.voting_power,
+                })
+            }
+            // Penalty Applied
+            x if x == Event::NAMES[2] => {
+                let pe = pb::EventSlashingPenaltyApplied::from_event(event.as_ref())?;
+                let identity_key = IdentityKey::try_from(
+                    pe.identity_key
+                        .ok_or(anyhow!("event missing identity_key"))?,
+                )?;
+                let penalty = pe
+                    .new_penalty
+                    .ok_or(anyhow!("event missing new_penalty"))?
+                    .inner;
+                Ok(Self::PenaltyApplied {
+                    identity_key,
+                    epoch_index: pe.epoch_index,
+                    penalty,
+                })
+            }
+            x => Err(anyhow!(format!("unrecognized event kind: {x}"))),
+        }
+    }
+}
+
+/// A component for indexing and retrieving the slashing of validators.
+#[derive(Debug)]
+pub struct Component {
+    event_strings: HashSet<&'static str>,
+}
+
+impl Component {
+    pub const TEMPLATES: [(&'static str, &'static str); 1] =
+        [("slashing", include_str!("../../templates/slashing.html"))];
+
+    pub fn new() -> Self {
+        let event_strings = Event::NAMES.into_iter().collect();
+        Self { event_strings }
+    }
+
+    pub fn attach_to_indexer(self, indexer: pindexer::Indexer) -> pindexer::Indexer {
+        indexer.with_index(self)
+    }
+
+    /// Fetch the validators slashed in a range of blocks.
+    ///
+    /// This will be sorted in reverse order, with at most [MAX_SLASHES] being returned.
+    pub async fn slashes(
+        pool: &PgPool,
+        pagination: &Pagination<i64>,
+    ) -> anyhow::Result<Vec<Slash>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    slashing_event.height,
+    slashing_event.created_at,
+    slashing_event.ik,
+    stake_validator_set.name,
+    reason,
+    evidence_height,
+    (SELECT penalty::DOUBLE PRECISION / 1000000 FROM slashing_penalty
+     WHERE slashing_penalty.ik = slashing_event.ik AND slashing_penalty.height = slashing_event.height
+     ORDER BY rowid DESC LIMIT 1)
+FROM slashing_event
+LEFT JOIN stake_validator_set ON stake_validator_set.ik = slashing_event.ik
+WHERE slashing_event.height BETWEEN $1 AND $2
+ORDER BY slashing_event.rowid DESC
+LIMIT $3;"#,
+        )
+        .bind(pagination.start)
+        .bind(pagination.stop)
+        .bind(i64::try_from(pagination.limit(MAX_SLASHES))?)
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch every time a specific validator was slashed, most recent first.
+    pub async fn validator_slashes(
+        pool: &PgPool,
+        identity: &IdentityKey,
+    ) -> anyhow::Result<Vec<Slash>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    slashing_event.height,
+    slashing_event.created_at,
+    slashing_event.ik,
+    stake_validator_set.name,
+    reason,
+    evidence_height,
+    (SELECT penalty::DOUBLE PRECISION / 1000000 FROM slashing_penalty
+     WHERE slashing_penalty.ik = slashing_event.ik AND slashing_penalty.height = slashing_event.height
+     ORDER BY rowid DESC LIMIT 1)
+FROM slashing_event
+LEFT JOIN stake_validator_set ON stake_validator_set.ik = slashing_event.ik
+WHERE slashing_event.ik = $1
+ORDER BY slashing_event.rowid DESC;"#,
+        )
+        .bind(identity.to_bytes())
+        .fetch_all(pool)
+        .await?)
+    }
+}
+
+#[async_trait]
+impl AppView for Component {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        dbtx.as_mut().execute(include_str!("slashing.sql")).await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        self.event_strings.contains(type_str)
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        let ctx = Context::fetch(src_db, event).await?;
+        Event::try_from(event)?.index(dbtx, &ctx).await
+    }
+}
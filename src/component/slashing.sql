@@ -0,0 +1,36 @@
+-- This component is responsible for tracking when and why validators were slashed.
+--
+-- Validators are jailed for downtime, and tombstoned for double signing,
+-- with the staking component applying a penalty to their delegators in both cases.
+
+-- Each time a validator was jailed or tombstoned.
+CREATE TABLE IF NOT EXISTS slashing_event (
+  -- The rowid of the event in the source database, which gives us a total ordering.
+  rowid BIGINT PRIMARY KEY,
+  ik BYTEA NOT NULL,
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  -- One of "downtime" or "double_sign".
+  reason TEXT NOT NULL,
+  -- The height of the misbehavior, for double signing.
+  evidence_height BIGINT,
+  -- The voting power of the validator when it was slashed, for double signing.
+  voting_power BIGINT
+);
+
+CREATE INDEX IF NOT EXISTS slashing_event_ik_idx ON slashing_event (ik, height);
+CREATE INDEX IF NOT EXISTS slashing_event_height_idx ON slashing_event (height);
+
+-- Each penalty applied to a validator.
+CREATE TABLE IF NOT EXISTS slashing_penalty (
+  rowid BIGINT PRIMARY KEY,
+  ik BYTEA NOT NULL,
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  -- The epoch this penalty applies to.
+  epoch_index BIGINT NOT NULL,
+  -- The compounded penalty for this epoch, where 10^8 means that everything is slashed.
+  penalty BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS slashing_penalty_ik_idx ON slashing_penalty (ik, height);
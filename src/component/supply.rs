@@ -0,0 +1,617 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+use axum::async_trait;
+use penumbra_asset::{
+    asset::{Id as AssetId, REGISTRY},
+    STAKING_TOKEN_ASSET_ID,
+};
+use penumbra_num::Amount;
+use penumbra_proto::{event::ProtoEvent, penumbra::core::component::funding::v1 as funding_pb};
+use penumbra_stake::{DelegationToken, IdentityKey, UnbondingToken};
+use pindexer::{AppView, ContextualizedEvent, PgTransaction};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sqlx::{
+    postgres::PgRow,
+    types::chrono::{DateTime, Utc},
+    Executor as _, PgPool, Postgres, Transaction,
+};
+
+use super::{action::Action, net_flow, Context, IndexedTransaction};
+use crate::pagination::Pagination;
+use crate::sql::{SQLAmount, SQLAssetId};
+
+/// The maximum number of epochs in the issuance history.
+const MAX_EPOCHS: u64 = 100;
+/// Used to annualize the growth of the supply.
+const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0;
+/// A penalty of this size means that everything was slashed.
+const PENALTY_SCALE: u128 = 100_000_000;
+
+/// The total supply of an asset.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetSupply {
+    #[serde_as(as = "DisplayFromStr")]
+    pub asset_id: AssetId,
+    /// Whether more was burned than minted, which means we're missing some history.
+    pub negative: bool,
+    #[serde_as(as = "DisplayFromStr")]
+    pub supply: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for AssetSupply {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (SQLAssetId, SQLAmount, SQLAmount) = sqlx::FromRow::from_row(row)?;
+        let (positive, supply) = net_flow(x.1.into(), x.2.into());
+        Ok(Self {
+            asset_id: x.0.into(),
+            negative: !positive,
+            supply,
+        })
+    }
+}
+
+/// The total supply of the staking token, counting delegations.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakingSupply {
+    /// The amount of the staking token itself, including any still unbonding.
+    #[serde_as(as = "DisplayFromStr")]
+    pub unbonded: Amount,
+    /// The amount of the staking token delegation tokens are worth.
+    #[serde_as(as = "DisplayFromStr")]
+    pub bonded: Amount,
+    #[serde_as(as = "DisplayFromStr")]
+    pub total: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for StakingSupply {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (SQLAmount, SQLAmount) = sqlx::FromRow::from_row(row)?;
+        let (unbonded, bonded) = (Amount::from(x.0), Amount::from(x.1));
+        Ok(Self {
+            unbonded,
+            bonded,
+            total: unbonded + bonded,
+        })
+    }
+}
+
+/// The total supply of the staking token at the end of an epoch, and how it changed.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpochSupply {
+    pub index: i64,
+    pub end_height: i64,
+    /// This is missing if we haven't indexed the last block of the epoch.
+    pub end_time: Option<DateTime<Utc>>,
+    /// The total supply, counting delegations.
+    #[serde_as(as = "DisplayFromStr")]
+    pub supply: Amount,
+    /// Whether the supply grew over this epoch, rather than shrinking.
+    pub inflow: bool,
+    /// The difference in supply with the previous epoch.
+    #[serde_as(as = "DisplayFromStr")]
+    pub issuance: Amount,
+    /// The annualized rate of growth of the supply, as a percentage.
+    pub inflation: Option<f64>,
+}
+
+/// Record a change to the supply of an asset.
+#[allow(clippy::too_many_arguments)]
+async fn insert_change<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    height: i64,
+    created_at: Option<DateTime<Utc>>,
+    asset_id: AssetId,
+    kind: &str,
+    mint: bool,
+    amount: Amount,
+    identity: Option<&IdentityKey>,
+    unbonding_start_height: Option<u64>,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+INSERT INTO supply_change
+VALUES (DEFAULT, $1, $2, $3, $4, $5, $6, $7, $8);"#,
+    )
+    .bind(height)
+    .bind(created_at)
+    .bind(SQLAssetId::from(asset_id))
+    .bind(kind)
+    .bind(mint)
+    .bind(SQLAmount::from(amount))
+    .bind(identity.map(|x| x.to_bytes()))
+    .bind(unbonding_start_height.map(i64::try_from).transpose()?)
+    .execute(dbtx.as_mut())
+    .await?;
+    Ok(())
+}
+
+/// Record the changes in supply made by a transaction: burning its fee, and converting tokens.
+async fn index_transaction<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    tx: &IndexedTransaction,
+) -> anyhow::Result<()> {
+    index_fee(
+        dbtx,
+        tx.height,
+        tx.created_at,
+        tx.fee_amount,
+        tx.fee_asset_id,
+    )
+    .await?;
+    for action in tx.actions.iter() {
+        index_action(dbtx, tx.height, tx.created_at, action).await?;
+    }
+    Ok(())
+}
+
+/// Record the burning of a transaction's fee.
+async fn index_fee<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    height: i64,
+    created_at: DateTime<Utc>,
+    fee_amount: Amount,
+    fee_asset_id: AssetId,
+) -> anyhow::Result<()> {
+    if fee_amount == Amount::zero() {
+        return Ok(());
+    }
+    insert_change(
+        dbtx,
+        height,
+        Some(created_at),
+        fee_asset_id,
+        "fee",
+        false,
+        fee_amount,
+        None,
+        None,
+    )
+    .await
+}
+
+/// Record the conversion between staking, delegation, and unbonding tokens, if an action does one.
+///
+/// Undelegating turns delegation tokens into unbonding tokens, which only become staking tokens
+/// again when claimed, after the unbonding period and any slashing.
+async fn index_action<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    height: i64,
+    created_at: DateTime<Utc>,
+    action: &Action,
+) -> anyhow::Result<()> {
+    match action {
+        Action::Delegate {
+            identity,
+            unbonded_amount,
+            delegation_amount,
+        } => {
+            insert_change(
+                dbtx,
+                height,
+                Some(created_at),
+                *STAKING_TOKEN_ASSET_ID,
+                "delegate",
+                false,
+                *unbonded_amount,
+                None,
+                None,
+            )
+            .await?;
+            insert_change(
+                dbtx,
+                height,
+                Some(created_at),
+                DelegationToken::new(*identity).id(),
+                "delegate",
+                true,
+                *delegation_amount,
+                Some(identity),
+                None,
+            )
+            .await
+        }
+        Action::Undelegate {
+            identity,
+            unbonded_amount,
+            delegation_amount,
+            unbonding_start_height,
+        } => {
+            insert_change(
+                dbtx,
+                height,
+                Some(created_at),
+                DelegationToken::new(*identity).id(),
+                "undelegate",
+                false,
+                *delegation_amount,
+                Some(identity),
+                None,
+            )
+            .await?;
+            insert_change(
+                dbtx,
+                height,
+                Some(created_at),
+                UnbondingToken::new(*identity, *unbonding_start_height).id(),
+                "undelegate",
+                true,
+                *unbonded_amount,
+                Some(identity),
+                Some(*unbonding_start_height),
+            )
+            .await
+        }
+        Action::UndelegateClaim {
+            identity,
+            unbonding_start_height,
+            penalty,
+        } => {
+            index_claim(
+                dbtx,
+                height,
+                created_at,
+                identity,
+                *unbonding_start_height,
+                *penalty,
+            )
+            .await
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Record the claim of some unbonding tokens, minting staking tokens in their place.
+///
+/// The amount claimed is shielded, so we assume that everything still unbonding is claimed.
+/// Claims can only happen after the epoch of the undelegations, so once all of the unbonding
+/// tokens are claimed, the totals come out right.
+async fn index_claim<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    height: i64,
+    created_at: DateTime<Utc>,
+    identity: &IdentityKey,
+    unbonding_start_height: u64,
+    penalty: u64,
+) -> anyhow::Result<()> {
+    let (minted, burned): (SQLAmount, SQLAmount) = sqlx::query_as(
+        r#"
+SELECT
+    COALESCE(SUM(amount) FILTER (WHERE mint), 0),
+    COALESCE(SUM(amount) FILTER (WHERE NOT mint), 0)
+FROM supply_change
+WHERE ik = $1 AND unbonding_start_height = $2;"#,
+    )
+    .bind(identity.to_bytes())
+    .bind(i64::try_from(unbonding_start_height)?)
+    .fetch_one(dbtx.as_mut())
+    .await?;
+    let unbonding = Amount::from(minted)
+        .checked_sub(&Amount::from(burned))
+        .unwrap_or_default();
+    if unbonding == Amount::zero() {
+        return Ok(());
+    }
+    // The multiplication is split up to avoid overflowing with large amounts.
+    let kept = PENALTY_SCALE.saturating_sub(u128::from(penalty));
+    let value = unbonding.value();
+    let unbonded =
+        Amount::from(value / PENALTY_SCALE * kept + value % PENALTY_SCALE * kept / PENALTY_SCALE);
+    insert_change(
+        dbtx,
+        height,
+        Some(created_at),
+        UnbondingToken::new(*identity, unbonding_start_height).id(),
+        "undelegate_claim",
+        false,
+        unbonding,
+        Some(identity),
+        Some(unbonding_start_height),
+    )
+    .await?;
+    insert_change(
+        dbtx,
+        height,
+        Some(created_at),
+        *STAKING_TOKEN_ASSET_ID,
+        "undelegate_claim",
+        true,
+        unbonded,
+        None,
+        None,
+    )
+    .await
+}
+
+/// Read the allocations of each asset at genesis.
+///
+/// Allocations of delegation tokens come with the validator they belong to.
+fn genesis_allocations(
+    app_state: &serde_json::Value,
+) -> anyhow::Result<Vec<(AssetId, Amount, Option<IdentityKey>)>> {
+    let content = match super::genesis_content(app_state)? {
+        Some(x) => x,
+        None => return Ok(Vec::new()),
+    };
+    let mut out = Vec::new();
+    for allocation in content
+        .shielded_pool_content
+        .map(|x| x.allocations)
+        .unwrap_or_default()
+    {
+        let amount = Amount::try_from(
+            allocation
+                .amount
+                .ok_or(anyhow!("genesis allocation missing amount"))?,
+        )?;
+        let identity = allocation
+            .denom
+            .parse::<DelegationToken>()
+            .ok()
+            .map(|x| x.validator());
+        let asset_id = REGISTRY
+            .parse_denom(&allocation.denom)
+            .ok_or(anyhow!("unrecognized genesis denom: {}", allocation.denom))?
+            .id();
+        out.push((asset_id, amount, identity));
+    }
+    Ok(out)
+}
+
+/// One of the possible events that we care about.
+#[derive(Clone, Debug)]
+enum Event {
+    /// A parsed version of [funding_pb::EventFundingStreamReward].
+    FundingStreamReward { amount: Amount },
+}
+
+impl Event {
+    const NAMES: [&'static str; 1] =
+        ["penumbra.core.component.funding.v1.EventFundingStreamReward"];
+
+    /// Index this event, using the handle to the postgres transaction.
+    async fn index<'d>(
+        self,
+        dbtx: &mut Transaction<'d, Postgres>,
+        ctx: &Context,
+    ) -> anyhow::Result<()> {
+        match self {
+            Event::FundingStreamReward { amount } => {
+                insert_change(
+                    dbtx,
+                    ctx.height,
+                    Some(ctx.created_at),
+                    *STAKING_TOKEN_ASSET_ID,
+                    "issuance",
+                    true,
+                    amount,
+                    None,
+                )
+                .await
+            }
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a ContextualizedEvent> for Event {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &'a ContextualizedEvent) -> Result<Self, Self::Error> {
+        match event.event.kind.as_str() {
+            // Funding Stream Reward
+            x if x == Event::NAMES[0] => {
+                let pe = funding_pb::EventFundingStreamReward::from_event(event.as_ref())?;
+                let amount = Amount::try_from(
+                    pe.reward_amount
+                        .ok_or(anyhow!("event missing reward_amount"))?,
+                )?;
+                Ok(Self::FundingStreamReward { amount })
+            }
+            x => Err(anyhow!(format!("unrecognized event kind: {x}"))),
+        }
+    }
+}
+
+/// A component for indexing and retrieving the supply of each asset.
+#[derive(Debug)]
+pub struct Component {
+    event_strings: HashSet<&'static str>,
+}
+
+impl Component {
+    pub const TEMPLATES: [(&'static str, &'static str); 1] =
+        [("supply", include_str!("../../templates/supply.html"))];
+
+    pub fn new() -> Self {
+        let event_strings = Event::NAMES.into_iter().collect();
+        Self { event_strings }
+    }
+
+    pub fn attach_to_indexer(self, indexer: pindexer::Indexer) -> pindexer::Indexer {
+        indexer.with_index(self)
+    }
+
+    /// Fetch the current supply of each asset.
+    pub async fn supplies(pool: &PgPool) -> anyhow::Result<Vec<AssetSupply>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    asset_id,
+    COALESCE(SUM(amount) FILTER (WHERE mint), 0),
+    COALESCE(SUM(amount) FILTER (WHERE NOT mint), 0)
+FROM (
+    SELECT asset_id, mint, amount FROM supply_change
+    UNION ALL
+    SELECT asset_id, mint, amount FROM supply_ibc_change
+) AS changes
+GROUP BY asset_id
+ORDER BY asset_id;"#,
+        )
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch the current supply of the staking token, valuing delegations at the latest rates.
+    pub async fn staking_supply(pool: &PgPool) -> anyhow::Result<StakingSupply> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    GREATEST((
+        SELECT COALESCE(SUM(CASE WHEN mint THEN amount ELSE -amount END), 0)
+        FROM supply_change
+        WHERE asset_id = $1 OR unbonding_start_height IS NOT NULL
+    ), 0)::NUMERIC(39, 0),
+    GREATEST((
+        SELECT COALESCE(FLOOR(SUM(tokens * COALESCE(rate.exchange_rate, 100000000) / 100000000)), 0)
+        FROM (
+            SELECT ik, SUM(CASE WHEN mint THEN amount ELSE -amount END) AS tokens
+            FROM supply_change
+            WHERE ik IS NOT NULL AND unbonding_start_height IS NULL
+            GROUP BY ik
+        ) AS delegations
+        LEFT JOIN LATERAL (
+            SELECT exchange_rate FROM validator_rate
+            WHERE validator_rate.ik = delegations.ik
+            ORDER BY epoch_index DESC
+            LIMIT 1
+        ) AS rate ON TRUE
+    ), 0)::NUMERIC(39, 0);"#,
+        )
+        .bind(SQLAssetId::from(*STAKING_TOKEN_ASSET_ID))
+        .fetch_one(pool)
+        .await?)
+    }
+
+    /// Fetch the supply of the staking token at the end of each epoch, in a given range of epoch indices.
+    ///
+    /// This will be sorted in order, with only the last [MAX_EPOCHS] finished epochs being returned.
+    pub async fn epoch_supplies(
+        pool: &PgPool,
+        pagination: &Pagination<i64>,
+    ) -> anyhow::Result<Vec<EpochSupply>> {
+        let rows: Vec<(i64, i64, Option<DateTime<Utc>>, SQLAmount)> = sqlx::query_as(
+            r#"
+SELECT
+    index,
+    end_height,
+    (SELECT created_at FROM block WHERE height = end_height),
+    GREATEST((
+        SELECT COALESCE(SUM(CASE WHEN mint THEN amount ELSE -amount END), 0)
+        FROM supply_change
+        WHERE (asset_id = $1 OR unbonding_start_height IS NOT NULL) AND height <= end_height
+    ) + (
+        SELECT COALESCE(FLOOR(SUM(tokens * COALESCE(rate.exchange_rate, 100000000) / 100000000)), 0)
+        FROM (
+            SELECT ik, SUM(CASE WHEN mint THEN amount ELSE -amount END) AS tokens
+            FROM supply_change
+            WHERE ik IS NOT NULL AND unbonding_start_height IS NULL AND height <= end_height
+            GROUP BY ik
+        ) AS delegations
+        LEFT JOIN LATERAL (
+            SELECT exchange_rate FROM validator_rate
+            WHERE validator_rate.ik = delegations.ik AND epoch_index <= epoch.index + 1
+            ORDER BY epoch_index DESC
+            LIMIT 1
+        ) AS rate ON TRUE
+    ), 0)::NUMERIC(39, 0)
+FROM epoch
+WHERE end_height IS NOT NULL AND index BETWEEN $2 - 1 AND $3
+ORDER BY index DESC
+LIMIT $4;"#,
+        )
+        .bind(SQLAssetId::from(*STAKING_TOKEN_ASSET_ID))
+        .bind(pagination.start)
+        .bind(pagination.stop)
+        // One more, to know how the supply changed over the first epoch.
+        .bind(i64::try_from(pagination.limit(MAX_EPOCHS))? + 1)
+        .fetch_all(pool)
+        .await?;
+
+        let mut out = Vec::with_capacity(rows.len());
+        let mut previous: Option<(Amount, Option<DateTime<Utc>>)> = None;
+        for (index, end_height, end_time, supply) in rows.into_iter().rev() {
+            let supply = Amount::from(supply);
+            if let Some((previous_supply, previous_time)) = previous {
+                let (inflow, issuance) = net_flow(supply, previous_supply);
+                let inflation = match (previous_time, end_time) {
+                    (Some(start), Some(end)) if end > start && previous_supply > Amount::zero() => {
+                        let sign = if inflow { 1.0 } else { -1.0 };
+                        let growth = issuance.value() as f64 / previous_supply.value() as f64;
+                        let years = (end - start).num_seconds() as f64 / SECONDS_PER_YEAR;
+                        Some(100.0 * sign * growth / years)
+                    }
+                    _ => None,
+                };
+                out.push(EpochSupply {
+                    index,
+                    end_height,
+                    end_time,
+                    supply,
+                    inflow,
+                    issuance,
+                    inflation,
+                });
+            }
+            previous = Some((supply, end_time));
+        }
+        Ok(out)
+    }
+}
+
+#[async_trait]
+impl AppView for Component {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        dbtx.as_mut().execute(include_str!("supply.sql")).await?;
+        // Not knowing the initial supply shouldn't prevent us from indexing anything else.
+        match genesis_allocations(app_state) {
+            Ok(allocations) => {
+                for (asset_id, amount, identity) in allocations {
+                    insert_change(
+                        dbtx,
+                        0,
+                        None,
+                        asset_id,
+                        "genesis",
+                        true,
+                        amount,
+                        identity.as_ref(),
+                        None,
+                    )
+                    .await?;
+                }
+            }
+            Err(e) => tracing::warn!(?e, "failed to read allocations from genesis"),
+        }
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        type_str == "tx" || self.event_strings.contains(type_str)
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        // The fee of a failed transaction stays on its row, but was never actually burned.
+        if event.event.kind == "tx" {
+            return match IndexedTransaction::fetch(src_db, event).await? {
+                Some(tx) if tx.succeeded() => index_transaction(dbtx, &tx).await,
+                _ => Ok(()),
+            };
+        }
+        let ctx = Context::fetch(src_db, event).await?;
+        Event::try_from(event)?.index(dbtx, &ctx).await
+    }
+}
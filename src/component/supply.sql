@@ -0,0 +1,46 @@
+-- This component is responsible for tracking the total supply of each asset.
+--
+-- Funds moving between accounts don't change the supply, only minting and burning does.
+
+-- Each change to the supply of an asset, besides those caused by IBC transfers.
+CREATE TABLE IF NOT EXISTS supply_change (
+  id BIGSERIAL PRIMARY KEY,
+  -- This is 0 for allocations at genesis.
+  height BIGINT NOT NULL,
+  -- This is missing for allocations at genesis.
+  created_at TIMESTAMPTZ,
+  asset_id BYTEA NOT NULL,
+  -- One of:
+  -- - "genesis", an allocation at genesis,
+  -- - "issuance", a reward paid to a funding stream,
+  -- - "fee", a transaction fee, which gets burned,
+  -- - "delegate", staking tokens becoming delegation tokens,
+  -- - "undelegate", delegation tokens becoming unbonding tokens,
+  -- - "undelegate_claim", unbonding tokens becoming staking tokens again, minus any penalty.
+  kind TEXT NOT NULL,
+  -- Whether this change minted or burned the amount.
+  mint BOOLEAN NOT NULL,
+  amount NUMERIC(39, 0) NOT NULL,
+  -- The validator, for changes to the supply of its delegation or unbonding tokens.
+  ik BYTEA,
+  -- The height at which unbonding started, for changes to the supply of unbonding tokens.
+  unbonding_start_height BIGINT
+);
+
+CREATE INDEX IF NOT EXISTS supply_change_asset_idx ON supply_change (asset_id, height);
+CREATE INDEX IF NOT EXISTS supply_change_ik_idx ON supply_change (ik, height);
+
+-- The changes to supply caused by IBC transfers.
+--
+-- Assets which came in through a channel are minted when received, and burned when sent back.
+-- Other assets are escrowed instead, which doesn't change their supply.
+CREATE OR REPLACE VIEW supply_ibc_change AS
+SELECT
+  height,
+  created_at,
+  asset_id,
+  'ibc' AS kind,
+  kind <> 'outbound' AS mint,
+  amount
+FROM ibc_transfer
+WHERE voucher;
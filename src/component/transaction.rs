@@ -0,0 +1,210 @@
+use axum::async_trait;
+use penumbra_asset::asset::Id as AssetId;
+use penumbra_num::Amount;
+use pindexer::{AppView, ContextualizedEvent, PgPool, PgTransaction};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use sqlx::{
+    postgres::PgRow,
+    types::chrono::{DateTime, Utc},
+    Executor as _,
+};
+
+use crate::component::{action::Action, IndexedTransaction};
+use crate::pagination::Pagination;
+use crate::sql::{SQLAmount, SQLAssetId};
+
+/// The maximum number of transactions returned in a single listing.
+const MAX_TRANSACTIONS: u64 = 100;
+
+/// A basic view of a transaction.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transaction {
+    /// The hash of the transaction, in hex.
+    pub hash: String,
+    /// The height of the block containing the transaction.
+    pub height: i64,
+    /// The position of the transaction within its block.
+    pub index: i32,
+    pub created_at: DateTime<Utc>,
+    /// The amount of the fee paid.
+    #[serde_as(as = "DisplayFromStr")]
+    pub fee_amount: Amount,
+    /// The asset the fee was paid in.
+    #[serde_as(as = "DisplayFromStr")]
+    pub fee_asset_id: AssetId,
+    /// The number of actions in the transaction.
+    pub action_count: i32,
+    /// The kind of each action, in order.
+    pub action_kinds: Vec<String>,
+    /// The result code of the transaction, which is 0 if it succeeded.
+    pub code: i64,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for Transaction {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (
+            String,
+            i64,
+            i32,
+            DateTime<Utc>,
+            SQLAmount,
+            SQLAssetId,
+            i32,
+            Vec<String>,
+            i64,
+        ) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            hash: x.0,
+            height: x.1,
+            index: x.2,
+            created_at: x.3,
+            fee_amount: x.4.into(),
+            fee_asset_id: x.5.into(),
+            action_count: x.6,
+            action_kinds: x.7,
+            code: x.8,
+        })
+    }
+}
+
+/// A component for indexing and retrieving information about transactions.
+#[derive(Debug, Clone, Copy)]
+pub struct Component {}
+
+impl Component {
+    pub const TEMPLATES: [(&'static str, &'static str); 2] = [
+        (
+            "transactions",
+            include_str!("../../templates/transactions.html"),
+        ),
+        (
+            "transaction",
+            include_str!("../../templates/transaction.html"),
+        ),
+    ];
+
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    pub fn attach_to_indexer(self, indexer: pindexer::Indexer) -> pindexer::Indexer {
+        indexer.with_index(self)
+    }
+
+    /// Fetch a list of transactions, in a given range of heights.
+    ///
+    /// This will be sorted in reverse order, with at most [MAX_TRANSACTIONS] being returned.
+    pub async fn transactions(
+        pool: &PgPool,
+        pagination: &Pagination<i64>,
+    ) -> anyhow::Result<Vec<Transaction>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    encode(hash, 'hex'), height, index, created_at, fee_amount, fee_asset_id, action_count, action_kinds, code
+FROM transaction
+WHERE height BETWEEN $1 AND $2
+ORDER BY height DESC, index DESC
+LIMIT $3;"#,
+        )
+        .bind(pagination.start)
+        .bind(pagination.stop)
+        .bind(i64::try_from(pagination.limit(MAX_TRANSACTIONS))?)
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch a specific transaction, by its hash in hex.
+    pub async fn transaction(pool: &PgPool, hash: &str) -> anyhow::Result<Transaction> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT
+    encode(hash, 'hex'), height, index, created_at, fee_amount, fee_asset_id, action_count, action_kinds, code
+FROM transaction
+WHERE hash = decode($1, 'hex');"#,
+        )
+        .bind(hash)
+        .fetch_one(pool)
+        .await?)
+    }
+
+    /// Fetch the decoded actions of a specific transaction, by its hash in hex.
+    pub async fn actions(pool: &PgPool, hash: &str) -> anyhow::Result<Vec<Action>> {
+        let rows: Vec<(String,)> = sqlx::query_as(
+            "SELECT data FROM transaction_action WHERE tx_hash = decode($1, 'hex') ORDER BY index;",
+        )
+        .bind(hash)
+        .fetch_all(pool)
+        .await?;
+        rows.into_iter()
+            .map(|(data,)| Ok(serde_json::from_str(&data)?))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl AppView for Component {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        _app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        dbtx.as_mut()
+            .execute(include_str!("transaction.sql"))
+            .await?;
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        type_str == "tx"
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        let tx = match IndexedTransaction::fetch(src_db, event).await? {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        let action_kinds: Vec<String> = tx.actions.iter().map(|x| x.kind().to_string()).collect();
+
+        sqlx::query(
+            r#"
+INSERT INTO transaction
+VALUES (decode($1, 'hex'), $2, $3, $4, $5, $6, $7, $8, $9);"#,
+        )
+        .bind(&tx.hash)
+        .bind(tx.height)
+        .bind(tx.index)
+        .bind(tx.created_at)
+        .bind(SQLAmount::from(tx.fee_amount))
+        .bind(SQLAssetId::from(tx.fee_asset_id))
+        .bind(i32::try_from(tx.actions.len())?)
+        .bind(action_kinds)
+        .bind(i64::from(tx.code))
+        .execute(dbtx.as_mut())
+        .await?;
+
+        for (index, action) in tx.actions.iter().enumerate() {
+            sqlx::query(
+                r#"
+INSERT INTO transaction_action
+VALUES (decode($1, 'hex'), $2, $3, $4);"#,
+            )
+            .bind(&tx.hash)
+            .bind(i32::try_from(index)?)
+            .bind(action.kind())
+            .bind(serde_json::to_string(action)?)
+            .execute(dbtx.as_mut())
+            .await?;
+        }
+
+        Ok(())
+    }
+}
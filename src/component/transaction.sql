@@ -0,0 +1,39 @@
+-- This component is responsible for processing transactions.
+--
+-- Transactions are decoded from the `tx_results` table of the cometbft sink.
+
+CREATE TABLE IF NOT EXISTS transaction (
+  -- The hash of the transaction.
+  hash BYTEA PRIMARY KEY,
+  -- The height of the block containing this transaction.
+  height BIGINT NOT NULL,
+  -- The position of this transaction inside of the block.
+  index INTEGER NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  -- The amount of the fee paid.
+  fee_amount NUMERIC(39, 0) NOT NULL,
+  -- The asset the fee was paid in.
+  fee_asset_id BYTEA NOT NULL,
+  -- How many actions the transaction contains.
+  action_count INTEGER NOT NULL,
+  -- The kind of each action, in order.
+  action_kinds TEXT[] NOT NULL,
+  -- The result code of the transaction.
+  --
+  -- Anything other than 0 means that the transaction failed, and had no effect.
+  code BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS transaction_height_idx ON transaction (height, index);
+
+-- The decoded actions of each transaction.
+CREATE TABLE IF NOT EXISTS transaction_action (
+  tx_hash BYTEA NOT NULL,
+  -- The position of this action inside of the transaction.
+  index INTEGER NOT NULL,
+  -- The kind of action, e.g. "swap".
+  kind TEXT NOT NULL,
+  -- The public contents of the action, as JSON.
+  data TEXT NOT NULL,
+  PRIMARY KEY (tx_hash, index)
+);
@@ -1,9 +1,77 @@
+use std::{collections::HashSet, fmt::Display, str::FromStr};
+
+use anyhow::anyhow;
+use axum::async_trait;
 use decaf377_rdsa::VerificationKeyBytes;
 use penumbra_keys::address::Address;
-use penumbra_stake::{validator, IdentityKey};
+use penumbra_num::Amount;
+use penumbra_proto::{event::ProtoEvent, penumbra::core::component::stake::v1 as pb};
+use penumbra_stake::{rate::RateData, validator, DelegationToken, IdentityKey};
+use pindexer::{AppView, ContextualizedEvent, PgTransaction};
 use serde::{self, Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
-use sqlx::{postgres::PgRow, PgPool};
+use sqlx::{
+    postgres::PgRow,
+    types::chrono::{DateTime, Utc},
+    Executor as _, PgPool, Postgres, Transaction,
+};
+
+use super::{action::Action, net_flow, Context, IndexedTransaction};
+use crate::sql::SQLAmount;
+
+/// The number of recent blocks over which uptime is measured.
+///
+/// This matches the default `signed_blocks_window` of the staking component.
+const UPTIME_WINDOW: i64 = 10_000;
+
+/// The number of days of delegation flow shown for a validator.
+const FLOW_DAYS: i32 = 30;
+/// The number of days over which the biggest movers are ranked.
+const MOVER_DAYS: i32 = 7;
+/// The maximum number of biggest movers returned.
+const MAX_MOVERS: i64 = 10;
+
+/// The states a validator can be in, in the order we display them.
+pub const STATES: [&str; 6] = [
+    "active",
+    "inactive",
+    "jailed",
+    "defined",
+    "tombstoned",
+    "disabled",
+];
+
+/// How to order a list of validators.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum ValidatorSort {
+    #[default]
+    VotingPower,
+    Apr,
+    Participation,
+}
+
+impl FromStr for ValidatorSort {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "power" => Ok(Self::VotingPower),
+            "apr" => Ok(Self::Apr),
+            "participation" => Ok(Self::Participation),
+            x => Err(anyhow!("invalid validator sort: {x}")),
+        }
+    }
+}
+
+impl Display for ValidatorSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::VotingPower => write!(f, "power"),
+            Self::Apr => write!(f, "apr"),
+            Self::Participation => write!(f, "participation"),
+        }
+    }
+}
 
 /// Represents a very basic view of a Validator
 #[serde_as]
@@ -16,15 +84,35 @@ pub struct ValidatorSummary {
     pub identity: IdentityKey,
     /// The voting power of the validator
     pub voting_power: i64,
+    /// The consensus state of the validator, e.g. "active"
+    pub state: String,
+    /// The bonding state of the validator, e.g. "bonded"
+    pub bonding_state: String,
+    /// The trailing annual return for delegators, as a percentage
+    pub apr: Option<f64>,
+    /// The share of proposals voted on, as a percentage
+    pub participation: Option<f64>,
 }
 
 impl<'r> sqlx::FromRow<'r, PgRow> for ValidatorSummary {
     fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
-        let x: (&'r str, [u8; 32], i64) = sqlx::FromRow::from_row(row)?;
+        let x: (
+            &'r str,
+            [u8; 32],
+            i64,
+            String,
+            String,
+            Option<f64>,
+            Option<f64>,
+        ) = sqlx::FromRow::from_row(row)?;
         Ok(ValidatorSummary {
             name: x.0.to_string(),
             identity: IdentityKey(VerificationKeyBytes::from(x.1)),
             voting_power: x.2,
+            state: x.3,
+            bonding_state: x.4,
+            apr: x.5,
+            participation: x.6,
         })
     }
 }
@@ -37,6 +125,85 @@ pub struct FundingStream {
     pub rate_bps: u16,
 }
 
+/// Separate the contribution to the community pool from the other funding streams of a validator.
+///
+/// The other funding streams are sorted by descending rate.
+fn split_funding_streams(definition: &validator::Validator) -> (u16, Vec<FundingStream>) {
+    let mut community_pool_rate_bps = 0u16;
+    let mut funding_streams = Vec::new();
+    for x in definition.funding_streams.iter() {
+        match x {
+            penumbra_stake::FundingStream::ToAddress { address, rate_bps } => {
+                funding_streams.push(FundingStream {
+                    address: address.clone(),
+                    rate_bps: *rate_bps,
+                })
+            }
+            penumbra_stake::FundingStream::ToCommunityPool { rate_bps } => {
+                community_pool_rate_bps += rate_bps
+            }
+        }
+    }
+    funding_streams.sort_by_key(|x| std::cmp::Reverse(x.rate_bps));
+    (community_pool_rate_bps, funding_streams)
+}
+
+/// A field of a validator definition which changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldChange {
+    pub field: String,
+    /// This is missing for the first definition of a validator.
+    pub before: Option<String>,
+    pub after: String,
+}
+
+/// A new definition of a validator, and how it differs from the previous one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DefinitionChange {
+    /// This is 0 for definitions present at genesis.
+    pub height: i64,
+    /// This is missing for definitions present at genesis.
+    pub created_at: Option<DateTime<Utc>>,
+    pub sequence_number: i64,
+    /// The total commission of the validator after this change, including the community pool.
+    pub commission_bps: u32,
+    /// The total commission of the validator before this change, if any.
+    pub previous_commission_bps: Option<u32>,
+    pub changes: Vec<FieldChange>,
+}
+
+/// The fields of a definition we show changes to, formatted for display.
+fn definition_fields(definition: &validator::Validator) -> [(&'static str, String); 5] {
+    let (community_pool_rate_bps, funding_streams) = split_funding_streams(definition);
+    let mut streams: Vec<String> = funding_streams
+        .iter()
+        .map(|x| format!("{:.2}% to {}", f64::from(x.rate_bps) / 100.0, x.address))
+        .collect();
+    if community_pool_rate_bps > 0 {
+        streams.push(format!(
+            "{:.2}% to the community pool",
+            f64::from(community_pool_rate_bps) / 100.0
+        ));
+    }
+    [
+        ("name", definition.name.clone()),
+        ("website", definition.website.clone()),
+        ("description", definition.description.clone()),
+        ("enabled", definition.enabled.to_string()),
+        ("funding streams", streams.join("\n")),
+    ]
+}
+
+/// The total commission taken by a validator, in basis points.
+fn commission_bps(definition: &validator::Validator) -> u32 {
+    let (community_pool_rate_bps, funding_streams) = split_funding_streams(definition);
+    funding_streams
+        .iter()
+        .map(|x| u32::from(x.rate_bps))
+        .sum::<u32>()
+        + u32::from(community_pool_rate_bps)
+}
+
 /// A full description of a validator.
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,11 +225,34 @@ pub struct Validator {
     pub funding_streams: Vec<FundingStream>,
     /// The contribution to the community pool
     pub community_pool_rate_bps: u16,
+    /// The consensus state of the validator, e.g. "active"
+    pub state: String,
+    /// The bonding state of the validator, e.g. "bonded"
+    pub bonding_state: String,
+    /// When an unbonding validator will finish unbonding
+    pub unbonds_at_height: Option<i64>,
+    /// The number of staking tokens a delegation token is worth
+    pub exchange_rate: Option<f64>,
+    /// The trailing annual return for delegators, as a percentage
+    pub apr: Option<f64>,
+    /// The share of proposals voted on, as a percentage
+    pub participation: Option<f64>,
 }
 
 impl<'r> sqlx::FromRow<'r, PgRow> for Validator {
     fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
-        let x: ([u8; 32], &'r str, i64, &'r str) = sqlx::FromRow::from_row(row)?;
+        let x: (
+            [u8; 32],
+            &'r str,
+            i64,
+            &'r str,
+            String,
+            String,
+            Option<i64>,
+            Option<f64>,
+            Option<f64>,
+            Option<f64>,
+        ) = sqlx::FromRow::from_row(row)?;
 
         let identity = IdentityKey(VerificationKeyBytes::from(x.0));
         let name = x.1.to_string();
@@ -76,25 +266,7 @@ impl<'r> sqlx::FromRow<'r, PgRow> for Validator {
         let website = definition.website;
         let enabled = definition.enabled;
 
-        let (community_pool_rate_bps, funding_streams) = {
-            let mut community_pool_rate_bps = 0u16;
-            let mut funding_streams = Vec::new();
-            for x in definition.funding_streams.iter() {
-                match x {
-                    penumbra_stake::FundingStream::ToAddress { address, rate_bps } => {
-                        funding_streams.push(FundingStream {
-                            address: address.clone(),
-                            rate_bps: *rate_bps,
-                        })
-                    }
-                    penumbra_stake::FundingStream::ToCommunityPool { rate_bps } => {
-                        community_pool_rate_bps += rate_bps
-                    }
-                }
-            }
-            funding_streams.sort_by_key(|x| std::cmp::Reverse(x.rate_bps));
-            (community_pool_rate_bps, funding_streams)
-        };
+        let (community_pool_rate_bps, funding_streams) = split_funding_streams(&definition);
 
         Ok(Self {
             identity,
@@ -105,13 +277,532 @@ impl<'r> sqlx::FromRow<'r, PgRow> for Validator {
             enabled,
             funding_streams,
             community_pool_rate_bps,
+            state: x.4,
+            bonding_state: x.5,
+            unbonds_at_height: x.6,
+            exchange_rate: x.7,
+            apr: x.8,
+            participation: x.9,
+        })
+    }
+}
+
+/// The voting power of a validator after some change.
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
+pub struct VotingPowerPoint {
+    pub height: i64,
+    pub created_at: DateTime<Utc>,
+    pub voting_power: i64,
+}
+
+/// A transition of a validator into a new state.
+#[derive(Debug, Clone, sqlx::FromRow, Serialize, Deserialize)]
+pub struct StateChange {
+    pub height: i64,
+    pub created_at: DateTime<Utc>,
+    /// One of "defined", "active", "inactive", "jailed", "tombstoned", or "disabled".
+    pub state: String,
+}
+
+/// A transition of some validator into a new state, as part of a change to the validator set.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetChange {
+    pub height: i64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub identity: IdentityKey,
+    /// The name of the validator, if it still exists.
+    pub name: Option<String>,
+    /// One of "defined", "active", "inactive", "jailed", "tombstoned", or "disabled".
+    pub state: String,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for SetChange {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (i64, [u8; 32], Option<String>, String) = sqlx::FromRow::from_row(row)?;
+        Ok(Self {
+            height: x.0,
+            identity: IdentityKey(VerificationKeyBytes::from(x.1)),
+            name: x.2,
+            state: x.3,
+        })
+    }
+}
+
+/// How reliably a validator has been signing blocks recently.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Uptime {
+    /// The number of recent blocks in which the validator was active, at most [UPTIME_WINDOW].
+    pub window: i64,
+    /// The number of blocks in this window the validator failed to sign.
+    pub missed: i64,
+    /// The percentage of blocks in this window the validator signed.
+    pub uptime: f64,
+}
+
+/// The delegations to and undelegations from a validator over a single day.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegationFlow {
+    pub day: DateTime<Utc>,
+    /// The amount of the staking token delegated.
+    #[serde_as(as = "DisplayFromStr")]
+    pub delegated: Amount,
+    /// The amount of the staking token undelegated.
+    #[serde_as(as = "DisplayFromStr")]
+    pub undelegated: Amount,
+    /// Whether more was delegated than undelegated.
+    pub inflow: bool,
+    /// The difference between the amounts delegated and undelegated.
+    #[serde_as(as = "DisplayFromStr")]
+    pub net: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for DelegationFlow {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: (DateTime<Utc>, SQLAmount, SQLAmount) = sqlx::FromRow::from_row(row)?;
+        let (delegated, undelegated) = (Amount::from(x.1), Amount::from(x.2));
+        let (inflow, net) = net_flow(delegated, undelegated);
+        Ok(Self {
+            day: x.0,
+            delegated,
+            undelegated,
+            inflow,
+            net,
         })
     }
 }
 
-/// A component for indexing and retrieving information about blocks.
-#[derive(Debug, Clone, Copy)]
-pub struct Component {}
+/// A validator whose delegations changed a lot recently.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DelegationMover {
+    #[serde_as(as = "DisplayFromStr")]
+    pub identity: IdentityKey,
+    pub name: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub delegated: Amount,
+    #[serde_as(as = "DisplayFromStr")]
+    pub undelegated: Amount,
+    /// Whether more was delegated than undelegated.
+    pub inflow: bool,
+    #[serde_as(as = "DisplayFromStr")]
+    pub net: Amount,
+}
+
+impl<'r> sqlx::FromRow<'r, PgRow> for DelegationMover {
+    fn from_row(row: &'r PgRow) -> Result<Self, sqlx::Error> {
+        let x: ([u8; 32], String, SQLAmount, SQLAmount) = sqlx::FromRow::from_row(row)?;
+        let (delegated, undelegated) = (Amount::from(x.2), Amount::from(x.3));
+        let (inflow, net) = net_flow(delegated, undelegated);
+        Ok(Self {
+            identity: IdentityKey(VerificationKeyBytes::from(x.0)),
+            name: x.1,
+            delegated,
+            undelegated,
+            inflow,
+            net,
+        })
+    }
+}
+
+/// Record the delegations, undelegations, and claims in a transaction.
+///
+/// These amounts are only present in actions, rather than in the events of the staking component.
+async fn index_transaction<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    tx: &IndexedTransaction,
+) -> anyhow::Result<()> {
+    for (index, action) in tx.actions.iter().enumerate() {
+        let (identity, unbonded_amount, delegation_amount) = match action {
+            Action::Delegate {
+                identity,
+                unbonded_amount,
+                delegation_amount,
+            }
+            | Action::Undelegate {
+                identity,
+                unbonded_amount,
+                delegation_amount,
+                ..
+            } => (
+                identity,
+                Some(SQLAmount::from(*unbonded_amount)),
+                Some(SQLAmount::from(*delegation_amount)),
+            ),
+            Action::UndelegateClaim { identity, .. } => (identity, None, None),
+            _ => continue,
+        };
+        sqlx::query(
+            r#"
+INSERT INTO validator_delegation
+VALUES (decode($1, 'hex'), $2, $3, $4, $5, $6, $7, $8);"#,
+        )
+        .bind(&tx.hash)
+        .bind(i32::try_from(index)?)
+        .bind(identity.to_bytes())
+        .bind(tx.height)
+        .bind(tx.created_at)
+        .bind(action.kind())
+        .bind(unbonded_amount)
+        .bind(delegation_amount)
+        .execute(dbtx.as_mut())
+        .await?;
+    }
+    Ok(())
+}
+
+/// The name we use for a given validator state.
+fn state_name(state: validator::State) -> &'static str {
+    match state {
+        validator::State::Defined => "defined",
+        validator::State::Inactive => "inactive",
+        validator::State::Active => "active",
+        validator::State::Jailed => "jailed",
+        validator::State::Tombstoned => "tombstoned",
+        validator::State::Disabled => "disabled",
+    }
+}
+
+/// The name we use for a given bonding state, and the height it will unbond at, if any.
+fn bonding_state_name(
+    state: validator::BondingState,
+) -> anyhow::Result<(&'static str, Option<i64>)> {
+    Ok(match state {
+        validator::BondingState::Bonded => ("bonded", None),
+        validator::BondingState::Unbonding { unbonds_at_height } => {
+            ("unbonding", Some(i64::try_from(unbonds_at_height)?))
+        }
+        validator::BondingState::Unbonded => ("unbonded", None),
+    })
+}
+
+/// Record that a validator is bonded and active from genesis.
+async fn insert_genesis_status<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    identity_key: IdentityKey,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+    INSERT INTO validator_status
+    VALUES ($1, 'active', 'bonded', NULL)
+    ON CONFLICT (ik) DO NOTHING;
+    "#,
+    )
+    .bind(identity_key.to_bytes())
+    .execute(dbtx.as_mut())
+    .await?;
+    Ok(())
+}
+
+/// Record a definition uploaded by a validator, or present at genesis.
+async fn insert_definition<'d>(
+    dbtx: &mut Transaction<'d, Postgres>,
+    height: i64,
+    created_at: Option<DateTime<Utc>>,
+    definition: &validator::Validator,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+    INSERT INTO validator_definition
+    VALUES ($1, $2, $3, $4, $5)
+    ON CONFLICT (ik, sequence_number) DO NOTHING;
+    "#,
+    )
+    .bind(definition.identity_key.to_bytes())
+    .bind(i64::from(definition.sequence_number))
+    .bind(height)
+    .bind(created_at)
+    .bind(serde_json::to_string(definition)?)
+    .execute(dbtx.as_mut())
+    .await?;
+    Ok(())
+}
+
+/// Read the definitions of the validators present at genesis.
+fn genesis_validators(app_state: &serde_json::Value) -> anyhow::Result<Vec<validator::Validator>> {
+    let content = match super::genesis_content(app_state)? {
+        Some(x) => x,
+        None => return Ok(Vec::new()),
+    };
+    content
+        .stake_content
+        .map(|x| x.validators)
+        .unwrap_or_default()
+        .into_iter()
+        .map(validator::Validator::try_from)
+        .collect()
+}
+
+/// Read the delegation tokens allocated at genesis, by validator.
+fn genesis_delegations(
+    app_state: &serde_json::Value,
+) -> anyhow::Result<Vec<(IdentityKey, Amount)>> {
+    let content = match super::genesis_content(app_state)? {
+        Some(x) => x,
+        None => return Ok(Vec::new()),
+    };
+    let mut out = Vec::new();
+    for allocation in content
+        .shielded_pool_content
+        .map(|x| x.allocations)
+        .unwrap_or_default()
+    {
+        // Most allocations are of other assets.
+        let token = match allocation.denom.parse::<DelegationToken>() {
+            Ok(x) => x,
+            Err(_) => continue,
+        };
+        let amount = Amount::try_from(
+            allocation
+                .amount
+                .ok_or(anyhow!("genesis allocation missing amount"))?,
+        )?;
+        out.push((token.validator(), amount));
+    }
+    Ok(out)
+}
+
+/// One of the possible events that we care about.
+#[derive(Clone, Debug)]
+enum Event {
+    /// A parsed version of [pb::EventValidatorVotingPowerChange].
+    VotingPowerChange {
+        identity_key: IdentityKey,
+        voting_power: Amount,
+    },
+    /// A parsed version of [pb::EventValidatorStateChange].
+    StateChange {
+        identity_key: IdentityKey,
+        state: validator::State,
+    },
+    /// A parsed version of [pb::EventValidatorMissedBlock].
+    MissedBlock { identity_key: IdentityKey },
+    /// A parsed version of [pb::EventValidatorBondingStateChange].
+    BondingStateChange {
+        identity_key: IdentityKey,
+        bonding_state: validator::BondingState,
+    },
+    /// A parsed version of [pb::EventRateDataChange].
+    RateDataChange { rate_data: RateData },
+    /// A parsed version of [pb::EventValidatorDefinitionUpload].
+    DefinitionUpload { definition: validator::Validator },
+}
+
+impl Event {
+    const NAMES: [&'static str; 6] = [
+        "penumbra.core.component.stake.v1.EventValidatorVotingPowerChange",
+        "penumbra.core.component.stake.v1.EventValidatorStateChange",
+        "penumbra.core.component.stake.v1.EventValidatorMissedBlock",
+        "penumbra.core.component.stake.v1.EventValidatorBondingStateChange",
+        "penumbra.core.component.stake.v1.EventRateDataChange",
+        "penumbra.core.component.stake.v1.EventValidatorDefinitionUpload",
+    ];
+
+    /// Index this event, using the handle to the postgres transaction.
+    async fn index<'d>(
+        self,
+        dbtx: &mut Transaction<'d, Postgres>,
+        ctx: &Context,
+    ) -> anyhow::Result<()> {
+        match self {
+            Event::VotingPowerChange {
+                identity_key,
+                voting_power,
+            } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO validator_voting_power
+                VALUES ($1, $2, $3, $4, $5);
+                "#,
+                )
+                .bind(ctx.rowid)
+                .bind(identity_key.to_bytes())
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .bind(i64::try_from(voting_power.value())?)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::StateChange {
+                identity_key,
+                state,
+            } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO validator_state_change
+                VALUES ($1, $2, $3, $4, $5);
+                "#,
+                )
+                .bind(ctx.rowid)
+                .bind(identity_key.to_bytes())
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .bind(state_name(state))
+                .execute(dbtx.as_mut())
+                .await?;
+                // Newly defined validators haven't had any stake bonded to them yet.
+                sqlx::query(
+                    r#"
+                INSERT INTO validator_status
+                VALUES ($1, $2, 'unbonded', NULL)
+                ON CONFLICT (ik) DO UPDATE SET state = EXCLUDED.state;
+                "#,
+                )
+                .bind(identity_key.to_bytes())
+                .bind(state_name(state))
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::MissedBlock { identity_key } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO validator_missed_block
+                VALUES ($1, $2)
+                ON CONFLICT (ik, height) DO NOTHING;
+                "#,
+                )
+                .bind(identity_key.to_bytes())
+                .bind(ctx.height)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::BondingStateChange {
+                identity_key,
+                bonding_state,
+            } => {
+                let (bonding_state, unbonds_at_height) = bonding_state_name(bonding_state)?;
+                sqlx::query(
+                    r#"
+                INSERT INTO validator_status
+                VALUES ($1, 'defined', $2, $3)
+                ON CONFLICT (ik) DO UPDATE SET
+                    bonding_state = EXCLUDED.bonding_state,
+                    unbonds_at_height = EXCLUDED.unbonds_at_height;
+                "#,
+                )
+                .bind(identity_key.to_bytes())
+                .bind(bonding_state)
+                .bind(unbonds_at_height)
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::RateDataChange { rate_data } => {
+                sqlx::query(
+                    r#"
+                INSERT INTO validator_rate
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (ik, epoch_index) DO UPDATE SET
+                    reward_rate = EXCLUDED.reward_rate,
+                    exchange_rate = EXCLUDED.exchange_rate;
+                "#,
+                )
+                .bind(rate_data.identity_key.to_bytes())
+                .bind(i64::try_from(rate_data.epoch_index)?)
+                .bind(ctx.height)
+                .bind(ctx.created_at)
+                .bind(SQLAmount::from(rate_data.validator_reward_rate))
+                .bind(SQLAmount::from(rate_data.validator_exchange_rate))
+                .execute(dbtx.as_mut())
+                .await?;
+                Ok(())
+            }
+            Event::DefinitionUpload { definition } => {
+                insert_definition(dbtx, ctx.height, Some(ctx.created_at), &definition).await
+            }
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a ContextualizedEvent> for Event {
+    type Error = anyhow::Error;
+
+    fn try_from(event: &'a ContextualizedEvent) -> Result<Self, Self::Error> {
+        match event.event.kind.as_str() {
+            // Voting Power Change
+            x if x == Event::NAMES[0] => {
+                let pe = pb::EventValidatorVotingPowerChange::from_event(event.as_ref())?;
+                let identity_key = IdentityKey::try_from(
+                    pe.identity_key
+                        .ok_or(anyhow!("event missing identity_key"))?,
+                )?;
+                let voting_power = Amount::try_from(
+                    pe.voting_power
+                        .ok_or(anyhow!("event missing voting_power"))?,
+                )?;
+                Ok(Self::VotingPowerChange {
+                    identity_key,
+                    voting_power,
+                })
+            }
+            // State Change
+            x if x == Event::NAMES[1] => {
+                let pe = pb::EventValidatorStateChange::from_event(event.as_ref())?;
+                let identity_key = IdentityKey::try_from(
+                    pe.identity_key
+                        .ok_or(anyhow!("event missing identity_key"))?,
+                )?;
+                let state =
+                    validator::State::try_from(pe.state.ok_or(anyhow!("event missing state"))?)?;
+                Ok(Self::StateChange {
+                    identity_key,
+                    state,
+                })
+            }
+            // Missed Block
+            x if x == Event::NAMES[2] => {
+                let pe = pb::EventValidatorMissedBlock::from_event(event.as_ref())?;
+                let identity_key = IdentityKey::try_from(
+                    pe.identity_key
+                        .ok_or(anyhow!("event missing identity_key"))?,
+                )?;
+                Ok(Self::MissedBlock { identity_key })
+            }
+            // Bonding State Change
+            x if x == Event::NAMES[3] => {
+                let pe = pb::EventValidatorBondingStateChange::from_event(event.as_ref())?;
+                let identity_key = IdentityKey::try_from(
+                    pe.identity_key
+                        .ok_or(anyhow!("event missing identity_key"))?,
+                )?;
+                let bonding_state = validator::BondingState::try_from(
+                    pe.bonding_state
+                        .ok_or(anyhow!("event missing bonding_state"))?,
+                )?;
+                Ok(Self::BondingStateChange {
+                    identity_key,
+                    bonding_state,
+                })
+            }
+            // Rate Data Change
+            x if x == Event::NAMES[4] => {
+                let pe = pb::EventRateDataChange::from_event(event.as_ref())?;
+                let rate_data =
+                    RateData::try_from(pe.rate_data.ok_or(anyhow!("event missing rate_data"))?)?;
+                Ok(Self::RateDataChange { rate_data })
+            }
+            // Definition Upload
+            x if x == Event::NAMES[5] => {
+                let pe = pb::EventValidatorDefinitionUpload::from_event(event.as_ref())?;
+                let definition = validator::Validator::try_from(
+                    pe.validator.ok_or(anyhow!("event missing validator"))?,
+                )?;
+                Ok(Self::DefinitionUpload { definition })
+            }
+            x => Err(anyhow!(format!("unrecognized event kind: {x}"))),
+        }
+    }
+}
+
+/// A component for indexing and retrieving information about validators.
+#[derive(Debug)]
+pub struct Component {
+    event_strings: HashSet<&'static str>,
+}
 
 impl Component {
     pub const TEMPLATES: [(&'static str, &'static str); 2] = [
@@ -123,18 +814,38 @@ impl Component {
     ];
 
     pub fn new() -> Self {
-        Self {}
+        let event_strings = Event::NAMES.into_iter().collect();
+        Self { event_strings }
     }
 
     pub fn attach_to_indexer(self, indexer: pindexer::Indexer) -> pindexer::Indexer {
-        indexer.with_index(pindexer::stake::ValidatorSet {})
+        indexer
+            .with_index(pindexer::stake::ValidatorSet {})
+            .with_index(self)
     }
 
-    /// Fetch a list of validators, in descending voting power.
-    pub async fn validators(pool: &PgPool) -> anyhow::Result<Vec<ValidatorSummary>> {
+    /// Fetch a list of validators, optionally in a given state, in descending order.
+    pub async fn validators(
+        pool: &PgPool,
+        state: Option<&str>,
+        sort: ValidatorSort,
+    ) -> anyhow::Result<Vec<ValidatorSummary>> {
         Ok(sqlx::query_as(
-            "SELECT name, ik, voting_power FROM stake_validator_set ORDER BY voting_power DESC;",
+            r#"
+SELECT name, stake_validator_set.ik, voting_power,
+    COALESCE(state, 'defined'), COALESCE(bonding_state, 'unbonded'), apr, participation
+FROM stake_validator_set
+LEFT JOIN validator_status ON stake_validator_set.ik = validator_status.ik
+LEFT JOIN validator_apr ON stake_validator_set.ik = validator_apr.ik
+LEFT JOIN governance_participation ON stake_validator_set.ik = governance_participation.ik
+WHERE $1::TEXT IS NULL OR COALESCE(state, 'defined') = $1
+ORDER BY
+    CASE WHEN $2 = 'apr' THEN apr END DESC NULLS LAST,
+    CASE WHEN $2 = 'participation' THEN participation END DESC NULLS LAST,
+    voting_power DESC;"#,
         )
+        .bind(state)
+        .bind(sort.to_string())
         .fetch_all(pool)
         .await?)
     }
@@ -142,10 +853,318 @@ impl Component {
     /// Fetch a specific validator, by identity key
     pub async fn validator(pool: &PgPool, identity: &IdentityKey) -> anyhow::Result<Validator> {
         Ok(sqlx::query_as(
-            "SELECT ik, name, voting_power, definition FROM stake_validator_set WHERE ik = $1;",
+            r#"
+SELECT stake_validator_set.ik, name, voting_power, definition,
+    COALESCE(state, 'defined'), COALESCE(bonding_state, 'unbonded'), unbonds_at_height,
+    exchange_rate, apr, participation
+FROM stake_validator_set
+LEFT JOIN validator_status ON stake_validator_set.ik = validator_status.ik
+LEFT JOIN validator_apr ON stake_validator_set.ik = validator_apr.ik
+LEFT JOIN governance_participation ON stake_validator_set.ik = governance_participation.ik
+WHERE stake_validator_set.ik = $1;"#,
         )
         .bind(identity.to_bytes())
         .fetch_one(pool)
         .await?)
     }
+
+    /// Fetch each change to the voting power of a validator, in order.
+    pub async fn voting_power_history(
+        pool: &PgPool,
+        identity: &IdentityKey,
+    ) -> anyhow::Result<Vec<VotingPowerPoint>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT height, created_at, voting_power
+FROM validator_voting_power
+WHERE ik = $1
+ORDER BY rowid;"#,
+        )
+        .bind(identity.to_bytes())
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch each state a validator has transitioned into, most recent first.
+    pub async fn state_history(
+        pool: &PgPool,
+        identity: &IdentityKey,
+    ) -> anyhow::Result<Vec<StateChange>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT height, created_at, state
+FROM validator_state_change
+WHERE ik = $1
+ORDER BY rowid DESC;"#,
+        )
+        .bind(identity.to_bytes())
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch the amount delegated to and undelegated from a validator each day,
+    /// over the last [FLOW_DAYS] days with any activity, in chronological order.
+    pub async fn delegation_flow(
+        pool: &PgPool,
+        identity: &IdentityKey,
+    ) -> anyhow::Result<Vec<DelegationFlow>> {
+        let mut out: Vec<DelegationFlow> = sqlx::query_as(
+            r#"
+SELECT
+    date_trunc('day', created_at) AS day,
+    COALESCE(SUM(unbonded_amount) FILTER (WHERE kind = 'delegate'), 0),
+    COALESCE(SUM(unbonded_amount) FILTER (WHERE kind = 'undelegate'), 0)
+FROM validator_delegation
+WHERE ik = $1
+GROUP BY day
+ORDER BY day DESC
+LIMIT $2;"#,
+        )
+        .bind(identity.to_bytes())
+        .bind(FLOW_DAYS)
+        .fetch_all(pool)
+        .await?;
+        out.reverse();
+        Ok(out)
+    }
+
+    /// Fetch the amount of delegation tokens for a validator currently in existence.
+    pub async fn delegation_tokens(
+        pool: &PgPool,
+        identity: &IdentityKey,
+    ) -> anyhow::Result<Amount> {
+        let (amount,): (SQLAmount,) = sqlx::query_as(
+            r#"
+SELECT
+    COALESCE((SELECT delegation_amount FROM validator_genesis_delegation WHERE ik = $1), 0)
+    + COALESCE((SELECT SUM(delegation_amount) FROM validator_delegation WHERE ik = $1 AND kind = 'delegate'), 0)
+    - COALESCE((SELECT SUM(delegation_amount) FROM validator_delegation WHERE ik = $1 AND kind = 'undelegate'), 0);"#,
+        )
+        .bind(identity.to_bytes())
+        .fetch_one(pool)
+        .await?;
+        Ok(amount.into())
+    }
+
+    /// Fetch the validators with the largest net delegation flow, in either direction,
+    /// over the last [MOVER_DAYS] days of indexed blocks.
+    ///
+    /// At most [MAX_MOVERS] are returned.
+    pub async fn biggest_movers(pool: &PgPool) -> anyhow::Result<Vec<DelegationMover>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT ik, name, delegated, undelegated
+FROM (
+    SELECT
+        ik,
+        COALESCE(SUM(unbonded_amount) FILTER (WHERE kind = 'delegate'), 0) AS delegated,
+        COALESCE(SUM(unbonded_amount) FILTER (WHERE kind = 'undelegate'), 0) AS undelegated
+    FROM validator_delegation
+    WHERE created_at >= (SELECT MAX(created_at) FROM block) - make_interval(days => $1)
+    GROUP BY ik
+) AS flows
+JOIN stake_validator_set USING (ik)
+ORDER BY ABS(delegated - undelegated) DESC
+LIMIT $2;"#,
+        )
+        .bind(MOVER_DAYS)
+        .bind(MAX_MOVERS)
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch every definition of a validator, and how it changed, most recent first.
+    pub async fn definition_history(
+        pool: &PgPool,
+        identity: &IdentityKey,
+    ) -> anyhow::Result<Vec<DefinitionChange>> {
+        let rows: Vec<(i64, Option<DateTime<Utc>>, i64, String)> = sqlx::query_as(
+            r#"
+SELECT height, created_at, sequence_number, definition
+FROM validator_definition
+WHERE ik = $1
+ORDER BY sequence_number;"#,
+        )
+        .bind(identity.to_bytes())
+        .fetch_all(pool)
+        .await?;
+        let mut out = Vec::new();
+        let mut previous: Option<validator::Validator> = None;
+        for (height, created_at, sequence_number, definition) in rows {
+            let definition: validator::Validator = serde_json::from_str(&definition)?;
+            let changes = match &previous {
+                None => definition_fields(&definition)
+                    .into_iter()
+                    .map(|(field, after)| FieldChange {
+                        field: field.to_string(),
+                        before: None,
+                        after,
+                    })
+                    .collect(),
+                Some(previous) => definition_fields(previous)
+                    .into_iter()
+                    .zip(definition_fields(&definition))
+                    .filter(|((_, before), (_, after))| before != after)
+                    .map(|((field, before), (_, after))| FieldChange {
+                        field: field.to_string(),
+                        before: Some(before),
+                        after,
+                    })
+                    .collect(),
+            };
+            out.push(DefinitionChange {
+                height,
+                created_at,
+                sequence_number,
+                commission_bps: commission_bps(&definition),
+                previous_commission_bps: previous.as_ref().map(commission_bps),
+                changes,
+            });
+            previous = Some(definition);
+        }
+        out.reverse();
+        Ok(out)
+    }
+
+    /// Fetch the state changes of every validator in a range of heights, in order.
+    ///
+    /// The range is unbounded above when `end_height` is missing.
+    pub async fn state_changes(
+        pool: &PgPool,
+        start_height: i64,
+        end_height: Option<i64>,
+    ) -> anyhow::Result<Vec<SetChange>> {
+        Ok(sqlx::query_as(
+            r#"
+SELECT height, validator_state_change.ik, name, state
+FROM validator_state_change
+LEFT JOIN stake_validator_set ON stake_validator_set.ik = validator_state_change.ik
+WHERE height >= $1 AND ($2::BIGINT IS NULL OR height <= $2)
+ORDER BY rowid;"#,
+        )
+        .bind(start_height)
+        .bind(end_height)
+        .fetch_all(pool)
+        .await?)
+    }
+
+    /// Fetch the uptime of a validator over the last [UPTIME_WINDOW] blocks.
+    ///
+    /// Only the heights at which the validator was active count towards the window,
+    /// and this will be `None` for validators which weren't active at all in that time.
+    pub async fn uptime(pool: &PgPool, identity: &IdentityKey) -> anyhow::Result<Option<Uptime>> {
+        let (tip,): (Option<i64>,) = sqlx::query_as("SELECT MAX(height) FROM block;")
+            .fetch_one(pool)
+            .await?;
+        let tip = match tip {
+            None => return Ok(None),
+            Some(x) => x,
+        };
+        // Validators present at genesis are active from the start, without a state change.
+        let (window, missed): (i64, i64) = sqlx::query_as(
+            r#"
+WITH changes AS (
+    SELECT rowid, height, state
+    FROM validator_state_change
+    WHERE ik = $1
+    UNION ALL
+    SELECT -1, 0, 'active'
+    FROM validator_definition
+    WHERE ik = $1 AND height = 0
+), spans AS (
+    SELECT
+        state,
+        height AS start_height,
+        LEAD(height, 1, $3 + 1) OVER (ORDER BY height, rowid) AS end_height
+    FROM changes
+), active AS (
+    SELECT GREATEST(start_height, $2) AS start_height, LEAST(end_height, $3 + 1) AS end_height
+    FROM spans
+    WHERE state = 'active' AND end_height > $2
+)
+SELECT
+    (SELECT COALESCE(SUM(end_height - start_height), 0)::BIGINT FROM active),
+    (
+        SELECT COUNT(*)
+        FROM validator_missed_block
+        JOIN active ON height >= active.start_height AND height < active.end_height
+        WHERE ik = $1
+    );"#,
+        )
+        .bind(identity.to_bytes())
+        .bind(tip - UPTIME_WINDOW + 1)
+        .bind(tip)
+        .fetch_one(pool)
+        .await?;
+        if window <= 0 {
+            return Ok(None);
+        }
+        Ok(Some(Uptime {
+            window,
+            missed,
+            uptime: 100.0 * (window - missed) as f64 / window as f64,
+        }))
+    }
+}
+
+#[async_trait]
+impl AppView for Component {
+    async fn init_chain(
+        &self,
+        dbtx: &mut PgTransaction,
+        app_state: &serde_json::Value,
+    ) -> anyhow::Result<()> {
+        dbtx.as_mut().execute(include_str!("validator.sql")).await?;
+        // Not knowing the state of some validators shouldn't prevent us from indexing anything else.
+        match genesis_validators(app_state) {
+            Ok(definitions) => {
+                for x in definitions.iter() {
+                    insert_genesis_status(dbtx, x.identity_key).await?;
+                    insert_definition(dbtx, 0, None, x).await?;
+                }
+            }
+            Err(e) => tracing::warn!(?e, "failed to read validators from genesis"),
+        }
+        match genesis_delegations(app_state) {
+            Ok(delegations) => {
+                for (identity_key, amount) in delegations {
+                    sqlx::query(
+                        r#"
+                    INSERT INTO validator_genesis_delegation
+                    VALUES ($1, $2)
+                    ON CONFLICT (ik) DO UPDATE SET
+                        delegation_amount = validator_genesis_delegation.delegation_amount + EXCLUDED.delegation_amount;
+                    "#,
+                    )
+                    .bind(identity_key.to_bytes())
+                    .bind(SQLAmount::from(amount))
+                    .execute(dbtx.as_mut())
+                    .await?;
+                }
+            }
+            Err(e) => tracing::warn!(?e, "failed to read delegations from genesis"),
+        }
+        Ok(())
+    }
+
+    fn is_relevant(&self, type_str: &str) -> bool {
+        type_str == "tx" || self.event_strings.contains(type_str)
+    }
+
+    #[tracing::instrument(skip_all, fields(height = event.block_height))]
+    async fn index_event(
+        &self,
+        dbtx: &mut PgTransaction,
+        event: &ContextualizedEvent,
+        src_db: &PgPool,
+    ) -> anyhow::Result<()> {
+        if event.event.kind == "tx" {
+            return match IndexedTransaction::fetch(src_db, event).await? {
+                Some(tx) if tx.succeeded() => index_transaction(dbtx, &tx).await,
+                _ => Ok(()),
+            };
+        }
+        let ctx = Context::fetch(src_db, event).await?;
+        Event::try_from(event)?.index(dbtx, &ctx).await
+    }
 }
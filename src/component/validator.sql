@@ -0,0 +1,126 @@
+-- This component is responsible for tracking the history of each validator.
+--
+-- The current state of each validator is kept in `stake_validator_set`, which
+-- pindexer maintains for us, so these tables only record how that state changed.
+
+-- Each change to the voting power of a validator.
+CREATE TABLE IF NOT EXISTS validator_voting_power (
+  -- The rowid of the event in the source database, which gives us a total ordering.
+  rowid BIGINT PRIMARY KEY,
+  ik BYTEA NOT NULL,
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  -- The voting power after this change.
+  voting_power BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS validator_voting_power_ik_idx ON validator_voting_power (ik, height);
+
+-- Each transition between validator states.
+CREATE TABLE IF NOT EXISTS validator_state_change (
+  rowid BIGINT PRIMARY KEY,
+  ik BYTEA NOT NULL,
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  -- One of "defined", "active", "inactive", "jailed", "tombstoned", or "disabled".
+  state TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS validator_state_change_ik_idx ON validator_state_change (ik, height);
+-- Used by `state_changes`, which looks up the changes during an epoch by height alone.
+CREATE INDEX IF NOT EXISTS validator_state_change_height_idx ON validator_state_change (height);
+
+-- Each block a validator failed to sign, while active.
+CREATE TABLE IF NOT EXISTS validator_missed_block (
+  ik BYTEA NOT NULL,
+  height BIGINT NOT NULL,
+  PRIMARY KEY (ik, height)
+);
+
+-- The current consensus and bonding state of each validator.
+--
+-- Validators present at genesis start out active and bonded, without any events.
+CREATE TABLE IF NOT EXISTS validator_status (
+  ik BYTEA PRIMARY KEY,
+  -- One of "defined", "active", "inactive", "jailed", "tombstoned", or "disabled".
+  state TEXT NOT NULL,
+  -- One of "bonded", "unbonding", or "unbonded".
+  bonding_state TEXT NOT NULL,
+  -- The height at which an unbonding validator will become unbonded.
+  unbonds_at_height BIGINT
+);
+
+-- Each delegation to, undelegation from, or claim of an undelegation from, a validator.
+CREATE TABLE IF NOT EXISTS validator_delegation (
+  -- The hash of the transaction, and the position of the action inside of it.
+  tx_hash BYTEA NOT NULL,
+  index INTEGER NOT NULL,
+  ik BYTEA NOT NULL,
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  -- One of "delegate", "undelegate", or "undelegate_claim".
+  kind TEXT NOT NULL,
+  -- The amount of the staking token moved, missing for claims.
+  unbonded_amount NUMERIC(39, 0),
+  -- The amount of delegation tokens minted or burned, missing for claims.
+  delegation_amount NUMERIC(39, 0),
+  PRIMARY KEY (tx_hash, index)
+);
+
+CREATE INDEX IF NOT EXISTS validator_delegation_ik_idx ON validator_delegation (ik, created_at);
+CREATE INDEX IF NOT EXISTS validator_delegation_created_at_idx ON validator_delegation (created_at);
+
+-- The delegation tokens of each validator allocated at genesis.
+CREATE TABLE IF NOT EXISTS validator_genesis_delegation (
+  ik BYTEA PRIMARY KEY,
+  delegation_amount NUMERIC(39, 0) NOT NULL
+);
+
+-- The rate data of each validator, for each epoch.
+CREATE TABLE IF NOT EXISTS validator_rate (
+  ik BYTEA NOT NULL,
+  epoch_index BIGINT NOT NULL,
+  height BIGINT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL,
+  -- The reward rate for delegators over this epoch, where 10^8 means 100%.
+  reward_rate NUMERIC(39, 0) NOT NULL,
+  -- The number of staking tokens a delegation token is worth, times 10^8.
+  exchange_rate NUMERIC(39, 0) NOT NULL,
+  PRIMARY KEY (ik, epoch_index)
+);
+
+-- The annualized return for delegators of each validator, over the last 30 days.
+--
+-- This is based on the growth of the exchange rate, which already accounts for
+-- the commission taken by the validator's funding streams.
+CREATE OR REPLACE VIEW validator_apr AS
+WITH latest AS (
+  SELECT DISTINCT ON (ik) ik, exchange_rate, created_at
+  FROM validator_rate
+  ORDER BY ik, epoch_index DESC
+), earliest AS (
+  SELECT DISTINCT ON (ik) ik, validator_rate.exchange_rate, validator_rate.created_at
+  FROM validator_rate JOIN latest USING (ik)
+  WHERE validator_rate.created_at >= latest.created_at - INTERVAL '30 days'
+  ORDER BY ik, epoch_index
+)
+SELECT
+  ik,
+  latest.exchange_rate::DOUBLE PRECISION / 100000000 AS exchange_rate,
+  (latest.exchange_rate::DOUBLE PRECISION / NULLIF(earliest.exchange_rate, 0)::DOUBLE PRECISION - 1)
+    * (EXTRACT(EPOCH FROM INTERVAL '1 year') / NULLIF(EXTRACT(EPOCH FROM latest.created_at - earliest.created_at), 0))
+    * 100 AS apr
+FROM latest JOIN earliest USING (ik);
+
+-- Every definition uploaded by each validator, including those present at genesis.
+CREATE TABLE IF NOT EXISTS validator_definition (
+  ik BYTEA NOT NULL,
+  sequence_number BIGINT NOT NULL,
+  -- This is 0 for definitions present at genesis.
+  height BIGINT NOT NULL,
+  -- This is missing for definitions present at genesis.
+  created_at TIMESTAMPTZ,
+  -- The full definition, as JSON.
+  definition TEXT NOT NULL,
+  PRIMARY KEY (ik, sequence_number)
+);
@@ -0,0 +1,101 @@
+//! This module provides a shared layer for displaying amounts of assets to humans.
+//!
+//! Templates can use the `value` filter, as in `{{ amount | value(asset_id) }}`,
+//! to display `12.5 penumbra` instead of a raw amount of base units, and the `denom`
+//! filter, as in `{{ asset_id | denom }}`, to display a denomination instead of an id.
+//! Amounts of the staking token can use the `stake` filter, as in `{{ amount | stake }}`.
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use minijinja::Environment;
+use penumbra_asset::{
+    asset::{Id as AssetId, Metadata},
+    STAKING_TOKEN_ASSET_ID, STAKING_TOKEN_DENOM,
+};
+use penumbra_num::Amount;
+
+/// Formats amounts and asset ids, using the metadata of the assets we know about.
+///
+/// This is cheap to clone, with all clones sharing the same set of known assets.
+#[derive(Debug, Clone)]
+pub struct AssetFormatter {
+    known: Arc<RwLock<HashMap<AssetId, Metadata>>>,
+}
+
+impl AssetFormatter {
+    /// Create a new formatter, which only knows about the staking token.
+    pub fn new() -> Self {
+        let out = Self {
+            known: Arc::new(RwLock::new(HashMap::new())),
+        };
+        out.update([STAKING_TOKEN_DENOM.clone()]);
+        out
+    }
+
+    /// Learn about the metadata of some assets.
+    pub fn update(&self, metadata: impl IntoIterator<Item = Metadata>) {
+        let mut known = self
+            .known
+            .write()
+            .expect("asset formatter lock should not be poisoned");
+        for x in metadata {
+            known.insert(x.id(), x);
+        }
+    }
+
+    fn metadata(&self, asset_id: &AssetId) -> Option<Metadata> {
+        self.known
+            .read()
+            .expect("asset formatter lock should not be poisoned")
+            .get(asset_id)
+            .cloned()
+    }
+
+    /// Format an amount of an asset, e.g. `12.5 penumbra`.
+    ///
+    /// Amounts of unknown assets are left in base units, next to the asset id.
+    pub fn format_value(&self, amount: Amount, asset_id: &AssetId) -> String {
+        match self.metadata(asset_id) {
+            Some(metadata) => {
+                let unit = metadata.default_unit();
+                format!("{} {}", unit.format_value(amount), unit)
+            }
+            None => format!("{} {}", amount, asset_id),
+        }
+    }
+
+    /// Format the denomination of an asset, falling back to its id, if unknown.
+    pub fn format_denom(&self, asset_id: &AssetId) -> String {
+        match self.metadata(asset_id) {
+            Some(metadata) => metadata.default_unit().to_string(),
+            None => asset_id.to_string(),
+        }
+    }
+
+    /// Add the filters using this formatter to a template environment.
+    pub fn add_filters(&self, environment: &mut Environment<'static>) {
+        let formatter = self.clone();
+        environment.add_filter("value", move |amount: String, asset_id: String| {
+            match (amount.parse::<Amount>(), asset_id.parse::<AssetId>()) {
+                (Ok(amount), Ok(asset_id)) => formatter.format_value(amount, &asset_id),
+                _ => format!("{} {}", amount, asset_id),
+            }
+        });
+        let formatter = self.clone();
+        environment.add_filter("stake", move |amount: String| {
+            match amount.parse::<Amount>() {
+                Ok(amount) => formatter.format_value(amount, &STAKING_TOKEN_ASSET_ID),
+                Err(_) => amount,
+            }
+        });
+        let formatter = self.clone();
+        environment.add_filter("denom", move |asset_id: String| {
+            match asset_id.parse::<AssetId>() {
+                Ok(asset_id) => formatter.format_denom(&asset_id),
+                Err(_) => asset_id,
+            }
+        });
+    }
+}
@@ -1,18 +1,33 @@
 #[derive(Clone, Debug)]
 pub struct Indexer {
     options: pindexer::Options,
+    cometbft_rpc_url: Option<String>,
 }
 
 impl Indexer {
-    pub fn new(options: pindexer::Options) -> Self {
-        Self { options }
+    pub fn new(options: pindexer::Options, cometbft_rpc_url: Option<String>) -> Self {
+        Self {
+            options,
+            cometbft_rpc_url,
+        }
     }
 
     pub async fn run(self) -> anyhow::Result<()> {
         let mut indexer = pindexer::Indexer::new(self.options);
-        indexer = crate::component::block::Component::new().attach_to_indexer(indexer);
+        indexer = crate::component::asset::Component::new().attach_to_indexer(indexer);
+        indexer = crate::component::block::Component::new(self.cometbft_rpc_url.as_deref())?
+            .attach_to_indexer(indexer);
+        indexer = crate::component::epoch::Component::new().attach_to_indexer(indexer);
+        // The governance views read the validator tables, so this has to come first.
         indexer = crate::component::validator::Component::new().attach_to_indexer(indexer);
+        indexer = crate::component::slashing::Component::new().attach_to_indexer(indexer);
+        indexer = crate::component::governance::Component::new().attach_to_indexer(indexer);
+        indexer = crate::component::community_pool::Component::new().attach_to_indexer(indexer);
+        indexer = crate::component::ibc::Component::new().attach_to_indexer(indexer);
+        indexer = crate::component::shielded_pool::Component::new().attach_to_indexer(indexer);
+        indexer = crate::component::supply::Component::new().attach_to_indexer(indexer);
         indexer = crate::component::dex::Component::new().attach_to_indexer(indexer);
+        indexer = crate::component::transaction::Component::new().attach_to_indexer(indexer);
         indexer.run().await?;
 
         Ok(())
@@ -1,5 +1,6 @@
 mod component;
 mod error;
+mod format;
 mod indexer;
 mod pagination;
 mod sql;
@@ -35,6 +36,9 @@ struct Options {
     /// The listening address, if the web server should be run
     #[clap(long)]
     web: Option<String>,
+    /// The RPC endpoint of a cometbft node, used to fetch block headers while indexing
+    #[clap(long)]
+    cometbft_rpc_url: Option<String>,
     #[clap(flatten)]
     indexer: pindexer::Options,
 }
@@ -45,7 +49,11 @@ async fn main() -> anyhow::Result<()> {
 
     let opt = Options::parse();
 
-    let state = AppState::create(opt.indexer.dst_database_url.as_ref()).await?;
+    let state = AppState::create(
+        opt.indexer.dst_database_url.as_ref(),
+        opt.indexer.src_database_url.as_ref(),
+    )
+    .await?;
     let web_server_handle = if let Some(web) = opt.web {
         let address = SocketAddr::from_str(web.as_ref())?;
         tokio::spawn(web::WebServer::new(state, address).run())
@@ -57,7 +65,7 @@ async fn main() -> anyhow::Result<()> {
         })
     };
 
-    let indexer_handle = indexer::Indexer::new(opt.indexer).run();
+    let indexer_handle = indexer::Indexer::new(opt.indexer, opt.cometbft_rpc_url).run();
 
     tokio::select! {
         x = web_server_handle => x?,
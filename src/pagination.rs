@@ -59,6 +59,12 @@ impl<C: Cursor> From<RawPagination<C>> for Pagination<C> {
     }
 }
 
+impl<C: Cursor> Default for Pagination<C> {
+    fn default() -> Self {
+        RawPagination::default().into()
+    }
+}
+
 #[allow(dead_code)]
 impl<C: Cursor + tracing::Value> Pagination<C> {
     pub fn limit(&self, no_more_than: u64) -> u64 {
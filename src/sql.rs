@@ -1,6 +1,7 @@
 //! This module provides various shims for encoding penumbra domain types into Postgres.
 use anyhow::anyhow;
 use penumbra_asset::asset::Id as AssetId;
+use penumbra_dex::lp::position::Id as PositionId;
 use penumbra_num::Amount;
 // This type implements the actual arbitrary number in postgres but as decimal points.
 use sqlx::{types::BigDecimal, Decode, Encode, Postgres, Type};
@@ -130,3 +131,53 @@ impl Type<Postgres> for SQLAssetId {
         <[u8; 32]>::type_info()
     }
 }
+
+/// Represents a [PositionId] that can be serialized and deserialized from SQL easily.
+#[derive(Debug, Clone, Copy)]
+pub struct SQLPositionId(PositionId);
+
+impl SQLPositionId {
+    pub fn new(position_id: PositionId) -> Self {
+        Self(position_id)
+    }
+
+    pub fn position_id(self) -> PositionId {
+        self.0
+    }
+}
+
+impl From<PositionId> for SQLPositionId {
+    fn from(value: PositionId) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<SQLPositionId> for PositionId {
+    fn from(value: SQLPositionId) -> Self {
+        value.position_id()
+    }
+}
+
+impl<'q> Encode<'q, Postgres> for SQLPositionId {
+    fn encode_by_ref(
+        &self,
+        buf: &mut <Postgres as sqlx::database::HasArguments<'q>>::ArgumentBuffer,
+    ) -> sqlx::encode::IsNull {
+        self.position_id().0.encode_by_ref(buf)
+    }
+}
+
+impl<'q> Decode<'q, Postgres> for SQLPositionId {
+    fn decode(
+        value: <Postgres as sqlx::database::HasValueRef<'q>>::ValueRef,
+    ) -> Result<Self, sqlx::error::BoxDynError> {
+        let bytes = <[u8; 32]>::decode(value)?;
+        Ok(PositionId(bytes).into())
+    }
+}
+
+impl Type<Postgres> for SQLPositionId {
+    fn type_info() -> <Postgres as sqlx::Database>::TypeInfo {
+        <[u8; 32]>::type_info()
+    }
+}
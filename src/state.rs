@@ -3,13 +3,25 @@ use serde::Serialize;
 use sqlx::PgPool;
 
 use crate::component;
+use crate::format::AssetFormatter;
 
-fn create_environment() -> anyhow::Result<Environment<'static>> {
+fn create_environment(formatter: &AssetFormatter) -> anyhow::Result<Environment<'static>> {
     let mut environment = Environment::new();
+    formatter.add_filters(&mut environment);
 
-    for (name, file) in [component::block::Component::TEMPLATE]
+    for (name, file) in component::block::Component::TEMPLATES
         .into_iter()
         .chain(component::validator::Component::TEMPLATES)
+        .chain(component::transaction::Component::TEMPLATES)
+        .chain(component::dex::Component::TEMPLATES)
+        .chain(component::asset::Component::TEMPLATES)
+        .chain(component::slashing::Component::TEMPLATES)
+        .chain(component::epoch::Component::TEMPLATES)
+        .chain(component::governance::Component::TEMPLATES)
+        .chain(component::community_pool::Component::TEMPLATES)
+        .chain(component::ibc::Component::TEMPLATES)
+        .chain(component::shielded_pool::Component::TEMPLATES)
+        .chain(component::supply::Component::TEMPLATES)
     {
         environment.add_template(name, file)?;
     }
@@ -22,17 +34,35 @@ fn create_environment() -> anyhow::Result<Environment<'static>> {
 pub struct AppState {
     /// The connection pool for postgres.
     pool: PgPool,
+    /// The connection pool for the database the cometbft sink writes to.
+    src_pool: PgPool,
     environment: Environment<'static>,
+    formatter: AssetFormatter,
 }
 
 impl AppState {
     /// Create an instance of this state.
     ///
     /// - `db_url` is used to connect to our postgres database.
-    pub async fn create(db_url: &str) -> anyhow::Result<Self> {
+    /// - `src_db_url` is used to connect to the database of the cometbft sink.
+    pub async fn create(db_url: &str, src_db_url: &str) -> anyhow::Result<Self> {
         let pool = PgPool::connect(db_url).await?;
-        let environment = create_environment()?;
-        Ok(Self { pool, environment })
+        let src_pool = PgPool::connect(src_db_url).await?;
+        let formatter = AssetFormatter::new();
+        let environment = create_environment(&formatter)?;
+        Ok(Self {
+            pool,
+            src_pool,
+            environment,
+            formatter,
+        })
+    }
+
+    /// Reload the metadata of the assets we know about, for formatting.
+    pub async fn refresh_assets(&self) -> anyhow::Result<()> {
+        self.formatter
+            .update(component::asset::Component::metadata(&self.pool).await?);
+        Ok(())
     }
 
     /// Get the database pool associated with this state.
@@ -40,6 +70,11 @@ impl AppState {
         &self.pool
     }
 
+    /// Get the database pool of the cometbft sink.
+    pub fn src_pool(&self) -> &PgPool {
+        &self.src_pool
+    }
+
     /// Render a template by name
     pub fn render_template<S: Serialize>(&self, name: &str, ctx: S) -> anyhow::Result<String> {
         Ok(self.environment.get_template(name)?.render(ctx)?)
@@ -0,0 +1,76 @@
+use axum::extract::Path;
+use axum::response::{Html, IntoResponse, Response};
+use axum::{extract::State, routing::get, Json, Router};
+use penumbra_asset::asset::Id as AssetId;
+use penumbra_num::Amount;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+use crate::component::asset::{Asset, Component};
+use crate::component::dex::{self, AssetActivity, AssetPair};
+use crate::error::Result;
+use crate::state::AppState;
+
+use super::common::AcceptsJson;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AssetsResponse {
+    assets: Vec<Asset>,
+}
+
+async fn all_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+) -> Result<Response> {
+    let resp = AssetsResponse {
+        assets: Component::assets(state.pool()).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[0].0, resp)?).into_response())
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AssetResponse {
+    #[serde_as(as = "DisplayFromStr")]
+    asset_id: AssetId,
+    /// This will be missing if we haven't learned the metadata of this asset.
+    metadata: Option<Asset>,
+    /// The amount of this asset held by the DEX.
+    #[serde_as(as = "DisplayFromStr")]
+    dex_value: Amount,
+    pairs: Vec<AssetPair>,
+    activity: Vec<AssetActivity>,
+}
+
+async fn single_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    Path(id): Path<String>,
+) -> Result<Response> {
+    let asset_id = Component::resolve(state.pool(), &id).await?;
+    let resp = AssetResponse {
+        asset_id,
+        metadata: Component::asset(state.pool(), asset_id).await?,
+        dex_value: dex::Component::asset_value(state.pool(), asset_id).await?,
+        pairs: dex::Component::asset_pairs(state.pool(), asset_id).await?,
+        activity: dex::Component::asset_activity(state.pool(), asset_id).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[1].0, resp)?).into_response())
+    }
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(all_handler))
+        // IBC denoms contain slashes, so the identifier has to capture the rest of the path.
+        .route("/*id", get(single_handler))
+}
@@ -1,3 +1,4 @@
+use axum::extract::Path;
 use axum::response::{Html, IntoResponse, Response};
 use axum::{extract::State, routing::get, Json, Router};
 use serde::{Deserialize, Serialize};
@@ -8,7 +9,8 @@ use crate::state::AppState;
 
 use super::common::AcceptsJson;
 
-use crate::component::block::{Block, Component};
+use crate::component::block::{Block, BlockDetail, Component};
+use crate::component::shielded_pool::{self, BlockActivity};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct BlocksResponse {
@@ -27,10 +29,37 @@ async fn handler(
     if json {
         Ok(Json(resp).into_response())
     } else {
-        Ok(Html(state.render_template(Component::TEMPLATE.0, resp)?).into_response())
+        Ok(Html(state.render_template(Component::TEMPLATES[0].0, resp)?).into_response())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BlockResponse {
+    #[serde(flatten)]
+    detail: BlockDetail,
+    /// The number of notes created and spent.
+    shielded: BlockActivity,
+}
+
+async fn single_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    Path(height): Path<i64>,
+) -> Result<Response> {
+    let resp = BlockResponse {
+        detail: Component::block(state.pool(), state.src_pool(), height).await?,
+        shielded: shielded_pool::Component::block_activity(state.pool(), height).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[1].0, resp)?).into_response())
     }
 }
 
 pub fn router() -> Router<AppState> {
-    Router::new().route("/", get(handler))
+    Router::new()
+        .route("/", get(handler))
+        .route("/:height", get(single_handler))
 }
@@ -0,0 +1,38 @@
+use axum::response::{Html, IntoResponse, Response};
+use axum::{extract::State, routing::get, Json, Router};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::pagination::Pagination;
+use crate::state::AppState;
+
+use super::common::AcceptsJson;
+
+use crate::component::community_pool::{Balance, Component, Flow};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CommunityPoolResponse {
+    balances: Vec<Balance>,
+    flows: Vec<Flow>,
+}
+
+async fn handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    pagination: Pagination<i64>,
+) -> Result<Response> {
+    let resp = CommunityPoolResponse {
+        balances: Component::balances(state.pool()).await?,
+        flows: Component::flows(state.pool(), &pagination).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[0].0, resp)?).into_response())
+    }
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/", get(handler))
+}
@@ -0,0 +1,275 @@
+use axum::extract::{Path, Query};
+use axum::response::{Html, IntoResponse, Response};
+use axum::{extract::State, routing::get, Json, Router};
+use penumbra_asset::asset::Id as AssetId;
+use penumbra_dex::{lp::position, TradingPair};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+
+use crate::error::Result;
+use crate::pagination::Pagination;
+use crate::state::AppState;
+
+use super::common::AcceptsJson;
+
+use crate::component::dex::{
+    Arb, ArbProfit, AssetBalance, Bucket, Candle, CandleWindow, Component, Position, PositionEvent,
+    Trade, ValuePoint,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ValueResponse {
+    assets: Vec<AssetBalance>,
+}
+
+async fn value_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+) -> Result<Response> {
+    let resp = ValueResponse {
+        assets: Component::value(state.pool()).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[0].0, resp)?).into_response())
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+struct AssetIdString {
+    #[serde_as(as = "DisplayFromStr")]
+    asset_id: AssetId,
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+struct BucketQuery {
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    bucket: Option<Bucket>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ValueHistoryResponse {
+    #[serde_as(as = "DisplayFromStr")]
+    asset_id: AssetId,
+    #[serde_as(as = "DisplayFromStr")]
+    bucket: Bucket,
+    history: Vec<ValuePoint>,
+}
+
+async fn value_history_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    Path(AssetIdString { asset_id }): Path<AssetIdString>,
+    Query(BucketQuery { bucket }): Query<BucketQuery>,
+) -> Result<Response> {
+    let bucket = bucket.unwrap_or_default();
+    let resp = ValueHistoryResponse {
+        asset_id,
+        bucket,
+        history: Component::value_history(state.pool(), asset_id, bucket).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[1].0, resp)?).into_response())
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+struct PairString {
+    #[serde_as(as = "DisplayFromStr")]
+    base: AssetId,
+    #[serde_as(as = "DisplayFromStr")]
+    quote: AssetId,
+}
+
+impl PairString {
+    fn trading_pair(&self) -> TradingPair {
+        TradingPair::new(self.base, self.quote)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PositionsResponse {
+    positions: Vec<Position>,
+}
+
+async fn positions_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+) -> Result<Response> {
+    let resp = PositionsResponse {
+        positions: Component::positions(state.pool(), None).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[2].0, resp)?).into_response())
+    }
+}
+
+async fn pair_positions_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    Path(pair): Path<PairString>,
+) -> Result<Response> {
+    let resp = PositionsResponse {
+        positions: Component::positions(state.pool(), Some(pair.trading_pair())).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[2].0, resp)?).into_response())
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize)]
+struct PositionIdString {
+    #[serde_as(as = "DisplayFromStr")]
+    position_id: position::Id,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PositionResponse {
+    position: Position,
+    history: Vec<PositionEvent>,
+}
+
+async fn position_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    Path(PositionIdString { position_id }): Path<PositionIdString>,
+) -> Result<Response> {
+    let resp = PositionResponse {
+        position: Component::position(state.pool(), position_id).await?,
+        history: Component::position_history(state.pool(), position_id).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[3].0, resp)?).into_response())
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TradesResponse {
+    #[serde_as(as = "DisplayFromStr")]
+    base: AssetId,
+    #[serde_as(as = "DisplayFromStr")]
+    quote: AssetId,
+    trades: Vec<Trade>,
+}
+
+async fn trades_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    Path(PairString { base, quote }): Path<PairString>,
+    pagination: Pagination<i64>,
+) -> Result<Response> {
+    let resp = TradesResponse {
+        base,
+        quote,
+        trades: Component::trades(state.pool(), base, quote, &pagination).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[4].0, resp)?).into_response())
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+struct WindowQuery {
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    window: Option<CandleWindow>,
+}
+
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CandlesResponse {
+    #[serde_as(as = "DisplayFromStr")]
+    base: AssetId,
+    #[serde_as(as = "DisplayFromStr")]
+    quote: AssetId,
+    #[serde_as(as = "DisplayFromStr")]
+    window: CandleWindow,
+    candles: Vec<Candle>,
+}
+
+async fn candles_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    Path(PairString { base, quote }): Path<PairString>,
+    Query(WindowQuery { window }): Query<WindowQuery>,
+) -> Result<Response> {
+    let window = window.unwrap_or_default();
+    let resp = CandlesResponse {
+        base,
+        quote,
+        window,
+        candles: Component::candles(state.pool(), base, quote, window).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[5].0, resp)?).into_response())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ArbsResponse {
+    profits: Vec<ArbProfit>,
+    arbs: Vec<Arb>,
+}
+
+async fn arbs_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    pagination: Pagination<i64>,
+) -> Result<Response> {
+    let resp = ArbsResponse {
+        profits: Component::arb_profits(state.pool()).await?,
+        arbs: Component::arbs(state.pool(), &pagination).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[6].0, resp)?).into_response())
+    }
+}
+
+/// The routes for the current state of the DEX.
+pub fn current_router() -> Router<AppState> {
+    Router::new()
+        .route("/value", get(value_handler))
+        .route("/positions", get(positions_handler))
+        .route("/positions/:position_id", get(position_handler))
+        .route("/pairs/:base/:quote/positions", get(pair_positions_handler))
+}
+
+/// The routes for the history of the DEX.
+pub fn history_router() -> Router<AppState> {
+    Router::new()
+        .route("/value/:asset_id", get(value_history_handler))
+        .route("/pairs/:base/:quote/trades", get(trades_handler))
+        .route("/candles/:base/:quote", get(candles_handler))
+        .route("/arbs", get(arbs_handler))
+}
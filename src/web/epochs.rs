@@ -0,0 +1,101 @@
+use axum::extract::Path;
+use axum::response::{Html, IntoResponse, Response};
+use axum::{extract::State, routing::get, Json, Router};
+use serde::{Deserialize, Serialize};
+
+use crate::component::supply::{self, EpochSupply};
+use crate::component::validator::{self, SetChange};
+use crate::error::Result;
+use crate::pagination::Pagination;
+use crate::state::AppState;
+
+use super::common::AcceptsJson;
+
+use crate::component::epoch::{Component, Epoch, EpochReward};
+
+/// An epoch, along with how much the supply of the staking token changed over it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EpochIssuance {
+    #[serde(flatten)]
+    epoch: Epoch,
+    /// This is missing for the current epoch, and the first one we know about.
+    issuance: Option<EpochSupply>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EpochsResponse {
+    epochs: Vec<EpochIssuance>,
+}
+
+/// The pagination here uses epoch indices, rather than heights.
+async fn handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    pagination: Pagination<i64>,
+) -> Result<Response> {
+    let mut supplies = supply::Component::epoch_supplies(state.pool(), &pagination).await?;
+    let resp = EpochsResponse {
+        epochs: Component::epochs(state.pool(), &pagination)
+            .await?
+            .into_iter()
+            .map(|epoch| EpochIssuance {
+                issuance: supplies
+                    .iter()
+                    .position(|x| x.index == epoch.index)
+                    .map(|i| supplies.swap_remove(i)),
+                epoch,
+            })
+            .collect(),
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[0].0, resp)?).into_response())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EpochResponse {
+    epoch: Epoch,
+    issuance: Option<EpochSupply>,
+    rewards: Vec<EpochReward>,
+    validator_changes: Vec<SetChange>,
+}
+
+async fn single_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    Path(index): Path<i64>,
+) -> Result<Response> {
+    let epoch = Component::epoch(state.pool(), index).await?;
+    let single = Pagination {
+        start: index,
+        stop: index,
+    };
+    let resp = EpochResponse {
+        issuance: supply::Component::epoch_supplies(state.pool(), &single)
+            .await?
+            .pop(),
+        rewards: Component::rewards(state.pool(), index).await?,
+        validator_changes: validator::Component::state_changes(
+            state.pool(),
+            epoch.start_height,
+            epoch.end_height,
+        )
+        .await?,
+        epoch,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[1].0, resp)?).into_response())
+    }
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(handler))
+        .route("/:index", get(single_handler))
+}
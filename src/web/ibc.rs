@@ -0,0 +1,98 @@
+use axum::extract::Path;
+use axum::response::{Html, IntoResponse, Response};
+use axum::{extract::State, routing::get, Json, Router};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::pagination::Pagination;
+use crate::state::AppState;
+
+use super::common::AcceptsJson;
+
+use crate::component::ibc::{
+    Channel, ChannelFlow, Component, Counterparty, Transfer, ValueBalance, ValueBalancePoint,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TransfersResponse {
+    transfers: Vec<Transfer>,
+}
+
+async fn transfers_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    pagination: Pagination<i64>,
+) -> Result<Response> {
+    let resp = TransfersResponse {
+        transfers: Component::transfers(state.pool(), &pagination).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[0].0, resp)?).into_response())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ChannelsResponse {
+    channels: Vec<Channel>,
+    flows: Vec<ChannelFlow>,
+    value_balances: Vec<ValueBalance>,
+    counterparties: Vec<Counterparty>,
+}
+
+async fn channels_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+) -> Result<Response> {
+    let resp = ChannelsResponse {
+        channels: Component::channels(state.pool()).await?,
+        flows: Component::channel_flows(state.pool()).await?,
+        value_balances: Component::value_balances(state.pool(), None).await?,
+        counterparties: Component::counterparties(state.pool()).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[1].0, resp)?).into_response())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ChannelResponse {
+    channel: Channel,
+    value_balances: Vec<ValueBalance>,
+    value_balance_history: Vec<ValueBalancePoint>,
+}
+
+async fn channel_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    Path(channel_id): Path<String>,
+) -> Result<Response> {
+    let resp = ChannelResponse {
+        channel: Component::channel(state.pool(), &channel_id).await?,
+        value_balances: Component::value_balances(state.pool(), Some(&channel_id)).await?,
+        value_balance_history: Component::value_balance_history(state.pool(), &channel_id).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[2].0, resp)?).into_response())
+    }
+}
+
+/// The routes for the current state of IBC.
+pub fn current_router() -> Router<AppState> {
+    Router::new()
+        .route("/channels", get(channels_handler))
+        .route("/channels/:channel_id", get(channel_handler))
+}
+
+/// The routes for the history of IBC.
+pub fn history_router() -> Router<AppState> {
+    Router::new().route("/transfers", get(transfers_handler))
+}
@@ -1,8 +1,17 @@
+mod assets;
 mod block;
 mod common;
+mod community_pool;
+mod dex;
+mod epochs;
 mod example;
+mod ibc;
 mod index;
+mod proposals;
+mod slashing;
 mod static_files;
+mod stats;
+mod transactions;
 mod validators;
 
 use axum::{
@@ -10,11 +19,15 @@ use axum::{
     Router,
 };
 use core::net::SocketAddr;
+use std::time::Duration;
 use tower_http::trace::TraceLayer;
 use tracing::info_span;
 
 use crate::state::AppState;
 
+/// How often to reload the metadata of assets.
+const ASSET_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
 /// Represents the configuration of the web server.
 ///
 /// This is the entry point to the frontend, and running it will serve the web pages.
@@ -35,12 +48,35 @@ impl WebServer {
     }
 
     pub async fn run(self) -> anyhow::Result<()> {
+        // New assets can appear at any time, so we periodically pick up their metadata.
+        let state = self.state.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(ASSET_REFRESH_INTERVAL);
+            loop {
+                interval.tick().await;
+                if let Err(e) = state.refresh_assets().await {
+                    tracing::warn!(?e, "failed to refresh asset metadata");
+                }
+            }
+        });
+
         let app = Router::new()
             .nest("/", index::router())
             .nest("/static", static_files::router())
             .nest("/example", example::router())
+            .nest("/assets", assets::router())
             .nest("/current/validators", validators::router())
+            .nest("/current/dex", dex::current_router())
+            .nest("/current/community-pool", community_pool::router())
+            .nest("/current/ibc", ibc::current_router())
             .nest("/history/blocks", block::router())
+            .nest("/history/transactions", transactions::router())
+            .nest("/history/dex", dex::history_router())
+            .nest("/history/slashing", slashing::router())
+            .nest("/history/epochs", epochs::router())
+            .nest("/history/proposals", proposals::router())
+            .nest("/history/ibc", ibc::history_router())
+            .nest("/stats", stats::router())
             .with_state(self.state)
             .layer(
                 TraceLayer::new_for_http().make_span_with(|request: &Request<_>| {
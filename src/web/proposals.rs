@@ -0,0 +1,65 @@
+use axum::extract::Path;
+use axum::response::{Html, IntoResponse, Response};
+use axum::{extract::State, routing::get, Json, Router};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::pagination::Pagination;
+use crate::state::AppState;
+
+use super::common::AcceptsJson;
+
+use crate::component::governance::{Component, DelegatorVotes, Proposal, ValidatorVote};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ProposalsResponse {
+    proposals: Vec<Proposal>,
+}
+
+/// The pagination here uses proposal ids, rather than heights.
+async fn handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    pagination: Pagination<i64>,
+) -> Result<Response> {
+    let resp = ProposalsResponse {
+        proposals: Component::proposals(state.pool(), &pagination).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[0].0, resp)?).into_response())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ProposalResponse {
+    proposal: Proposal,
+    validator_votes: Vec<ValidatorVote>,
+    delegator_votes: Vec<DelegatorVotes>,
+}
+
+async fn single_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    Path(id): Path<i64>,
+) -> Result<Response> {
+    let resp = ProposalResponse {
+        proposal: Component::proposal(state.pool(), id).await?,
+        validator_votes: Component::validator_votes(state.pool(), id).await?,
+        delegator_votes: Component::delegator_votes(state.pool(), id).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[1].0, resp)?).into_response())
+    }
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(handler))
+        .route("/:id", get(single_handler))
+}
@@ -0,0 +1,36 @@
+use axum::response::{Html, IntoResponse, Response};
+use axum::{extract::State, routing::get, Json, Router};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::pagination::Pagination;
+use crate::state::AppState;
+
+use super::common::AcceptsJson;
+
+use crate::component::slashing::{Component, Slash};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SlashesResponse {
+    slashes: Vec<Slash>,
+}
+
+async fn handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    pagination: Pagination<i64>,
+) -> Result<Response> {
+    let resp = SlashesResponse {
+        slashes: Component::slashes(state.pool(), &pagination).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[0].0, resp)?).into_response())
+    }
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new().route("/", get(handler))
+}
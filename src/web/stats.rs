@@ -0,0 +1,65 @@
+use axum::response::{Html, IntoResponse, Response};
+use axum::{extract::State, routing::get, Json, Router};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::pagination::Pagination;
+use crate::state::AppState;
+
+use super::common::AcceptsJson;
+
+use crate::component::shielded_pool::{self, DailyActivity};
+use crate::component::supply::{self, AssetSupply, EpochSupply, StakingSupply};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ShieldedResponse {
+    days: Vec<DailyActivity>,
+}
+
+async fn shielded_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+) -> Result<Response> {
+    let resp = ShieldedResponse {
+        days: shielded_pool::Component::daily_activity(state.pool()).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(
+            Html(state.render_template(shielded_pool::Component::TEMPLATES[0].0, resp)?)
+                .into_response(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SupplyResponse {
+    staking: StakingSupply,
+    assets: Vec<AssetSupply>,
+    epochs: Vec<EpochSupply>,
+}
+
+async fn supply_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+) -> Result<Response> {
+    let resp = SupplyResponse {
+        staking: supply::Component::staking_supply(state.pool()).await?,
+        assets: supply::Component::supplies(state.pool()).await?,
+        epochs: supply::Component::epoch_supplies(state.pool(), &Pagination::default()).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(supply::Component::TEMPLATES[0].0, resp)?).into_response())
+    }
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/shielded", get(shielded_handler))
+        .route("/supply", get(supply_handler))
+}
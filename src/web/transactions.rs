@@ -0,0 +1,63 @@
+use axum::extract::Path;
+use axum::response::{Html, IntoResponse, Response};
+use axum::{extract::State, routing::get, Json, Router};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::pagination::Pagination;
+use crate::state::AppState;
+
+use super::common::AcceptsJson;
+
+use crate::component::action::Action;
+use crate::component::transaction::{Component, Transaction};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TransactionsResponse {
+    transactions: Vec<Transaction>,
+}
+
+async fn all_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    pagination: Pagination<i64>,
+) -> Result<Response> {
+    let resp = TransactionsResponse {
+        transactions: Component::transactions(state.pool(), &pagination).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[0].0, resp)?).into_response())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TransactionResponse {
+    transaction: Transaction,
+    actions: Vec<Action>,
+}
+
+async fn single_handler(
+    State(state): State<AppState>,
+    AcceptsJson(json): AcceptsJson,
+    Path(hash): Path<String>,
+) -> Result<Response> {
+    let resp = TransactionResponse {
+        transaction: Component::transaction(state.pool(), &hash).await?,
+        actions: Component::actions(state.pool(), &hash).await?,
+    };
+
+    if json {
+        Ok(Json(resp).into_response())
+    } else {
+        Ok(Html(state.render_template(Component::TEMPLATES[1].0, resp)?).into_response())
+    }
+}
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(all_handler))
+        .route("/:hash", get(single_handler))
+}
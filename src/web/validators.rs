@@ -1,6 +1,8 @@
-use axum::extract::Path;
+use anyhow::anyhow;
+use axum::extract::{Path, Query};
 use axum::response::{Html, IntoResponse, Response};
 use axum::{extract::State, routing::get, Json, Router};
+use penumbra_num::Amount;
 use penumbra_stake::IdentityKey;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
@@ -10,19 +12,57 @@ use crate::state::AppState;
 
 use super::common::AcceptsJson;
 
-use crate::component::validator::{Component, Validator, ValidatorSummary};
+use crate::component::governance::{self, VotingRecord};
+use crate::component::slashing::{self, Slash};
+use crate::component::validator::{
+    Component, DefinitionChange, DelegationFlow, DelegationMover, StateChange, Uptime, Validator,
+    ValidatorSort, ValidatorSummary, VotingPowerPoint, STATES,
+};
 
+#[serde_as]
+#[derive(Debug, Deserialize)]
+struct ValidatorsQuery {
+    #[serde(default)]
+    state: Option<String>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    sort: Option<ValidatorSort>,
+}
+
+#[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ValidatorsResponse {
+    /// The state the validators were filtered by, if any.
+    state: Option<String>,
+    /// Every possible state, in the order validators are grouped by.
+    states: Vec<String>,
+    #[serde_as(as = "DisplayFromStr")]
+    sort: ValidatorSort,
     validators: Vec<ValidatorSummary>,
+    /// The validators with the largest recent changes in delegations.
+    movers: Vec<DelegationMover>,
 }
 
 async fn all_handler(
     State(state): State<AppState>,
     AcceptsJson(json): AcceptsJson,
+    Query(ValidatorsQuery {
+        state: validator_state,
+        sort,
+    }): Query<ValidatorsQuery>,
 ) -> Result<Response> {
+    if let Some(x) = &validator_state {
+        if !STATES.contains(&x.as_str()) {
+            return Err(anyhow!("unknown validator state: {}", x).into());
+        }
+    }
+    let sort = sort.unwrap_or_default();
     let resp = ValidatorsResponse {
-        validators: Component::validators(state.pool()).await?,
+        validators: Component::validators(state.pool(), validator_state.as_deref(), sort).await?,
+        sort,
+        state: validator_state,
+        states: STATES.into_iter().map(String::from).collect(),
+        movers: Component::biggest_movers(state.pool()).await?,
     };
 
     if json {
@@ -39,9 +79,21 @@ struct IdentityKeyString {
     ik: IdentityKey,
 }
 
+#[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct ValidatorResponse {
     validator: Validator,
+    /// This will be missing if the validator has never been active.
+    uptime: Option<Uptime>,
+    voting_power_history: Vec<VotingPowerPoint>,
+    state_history: Vec<StateChange>,
+    slashes: Vec<Slash>,
+    /// The amount of delegation tokens currently in existence.
+    #[serde_as(as = "DisplayFromStr")]
+    delegation_tokens: Amount,
+    delegation_flow: Vec<DelegationFlow>,
+    definition_history: Vec<DefinitionChange>,
+    voting_record: Vec<VotingRecord>,
 }
 
 async fn single_handler(
@@ -51,6 +103,14 @@ async fn single_handler(
 ) -> Result<Response> {
     let resp = ValidatorResponse {
         validator: Component::validator(state.pool(), &ik).await?,
+        uptime: Component::uptime(state.pool(), &ik).await?,
+        voting_power_history: Component::voting_power_history(state.pool(), &ik).await?,
+        state_history: Component::state_history(state.pool(), &ik).await?,
+        slashes: slashing::Component::validator_slashes(state.pool(), &ik).await?,
+        delegation_tokens: Component::delegation_tokens(state.pool(), &ik).await?,
+        delegation_flow: Component::delegation_flow(state.pool(), &ik).await?,
+        definition_history: Component::definition_history(state.pool(), &ik).await?,
+        voting_record: governance::Component::validator_record(state.pool(), &ik).await?,
     };
 
     if json {